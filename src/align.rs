@@ -0,0 +1,101 @@
+// cuadra::align
+//
+//! Alignment and anchoring.
+//
+
+/// The alignment of a child along one axis of its parent.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Align {
+    /// Aligned at the start (left or top) of the parent.
+    #[default]
+    Start,
+    /// Centered in the parent, rounding towards the start.
+    Center,
+    /// Aligned at the end (right or bottom) of the parent.
+    End,
+    /// Stretched to the full length of the parent.
+    Stretch,
+}
+
+/// One of the nine anchor points of a zone.
+///
+/// The top side is the one towards the lower `y` values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Anchor {
+    #[default]
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    /// Returns the horizontal and vertical alignments equivalent to this anchor.
+    pub const fn aligns(&self) -> (Align, Align) {
+        use {Align::*, Anchor as A};
+        match self {
+            A::TopLeft => (Start, Start),
+            A::Top => (Center, Start),
+            A::TopRight => (End, Start),
+            A::Left => (Start, Center),
+            A::Center => (Center, Center),
+            A::Right => (End, Center),
+            A::BottomLeft => (Start, End),
+            A::Bottom => (Center, End),
+            A::BottomRight => (End, End),
+        }
+    }
+}
+
+macro_rules! align {
+    // $i: inner primitive
+    // $b: bit size
+    ( $($i:ty, $b:expr),+ ) => {
+        $( align![single: $i, $b]; )+
+    };
+
+    (single: $i:ty, $b:literal) => { paste::paste! {
+        use super::{[<Position$b>], [<Size$b>], [<Zone$b>]};
+
+        /// # alignment
+        impl [<Zone$b>] {
+            /// Returns a zone with the given `size`, placed inside this zone
+            /// with the given horizontal and vertical alignments.
+            ///
+            /// A child longer than this zone overflows it at the opposite end
+            /// of its alignment, or at both ends when centered.
+            pub const fn place(&self, size: [<Size$b>], h_align: Align, v_align: Align) -> [<Zone$b>] {
+                let (x, w) = [<align_$i>](self.x(), self.w(), size.w(), h_align);
+                let (y, h) = [<align_$i>](self.y(), self.h(), size.h(), v_align);
+                Self::new_raw(x, y, w, h)
+            }
+
+            /// Returns the position of the given `anchor` point,
+            /// which is always inside the zone.
+            ///
+            /// The center rounds towards the top-left.
+            pub const fn anchor(&self, anchor: Anchor) -> [<Position$b>] {
+                let (h_align, v_align) = anchor.aligns();
+                let (x, _) = [<align_$i>](self.x(), self.w(), 1, h_align);
+                let (y, _) = [<align_$i>](self.y(), self.h(), 1, v_align);
+                [<Position$b>]::new(x, y)
+            }
+        }
+
+        /// Returns the start and length of a child aligned inside a parent.
+        const fn [<align_$i>](start: $i, parent: $i, child: $i, align: Align) -> ($i, $i) {
+            match align {
+                Align::Start => (start, child),
+                Align::Center => (start + (parent - child) / 2, child),
+                Align::End => (start + parent - child, child),
+                Align::Stretch => (start, parent),
+            }
+        }
+    }};
+}
+align![i8, 8, i16, 16, i32, 32, i64, 64];
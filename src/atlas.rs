@@ -0,0 +1,238 @@
+// cuadra::atlas
+//
+//! Online rectangle allocation.
+//
+
+use crate::{pack::prune_contained, Position32, Size32, Zone32};
+use alloc::vec::Vec;
+
+/// The identifier of an allocation in an [`AtlasAllocator`].
+///
+/// It stays unique after deallocation, so a stale identifier can't
+/// deallocate a later allocation that happens to reuse its slot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AllocId {
+    index: u32,
+    generation: u32,
+}
+
+/// Statistics of an [`AtlasAllocator`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AtlasStats {
+    /// The number of live allocations.
+    pub allocations: usize,
+    /// The area not covered by any allocation.
+    pub free_area: u64,
+    /// The free rectangle with the largest area, if any.
+    pub largest_free: Option<Zone32>,
+}
+
+#[derive(Clone, Debug)]
+struct Slot {
+    generation: u32,
+    zone: Option<Zone32>,
+}
+
+/// Allocates and deallocates rectangles inside an atlas of a given size.
+///
+/// The free space is tracked as the list of maximal, possibly overlapping,
+/// free rectangles. Allocations take the one that fits them best by area,
+/// then the leftmost and topmost one, and are placed at its top-left corner.
+/// Deallocated rectangles are added back together with the maximal rectangles
+/// spanning them and their free neighbors, so that the free space doesn't
+/// fragment over time.
+#[derive(Clone, Debug)]
+pub struct AtlasAllocator {
+    size: Size32,
+    free: Vec<Zone32>,
+    slots: Vec<Slot>,
+    vacant: Vec<u32>,
+}
+
+impl AtlasAllocator {
+    /// Returns a new empty allocator for an atlas of the given `size`,
+    /// with its origin at `0, 0`.
+    pub fn new(size: Size32) -> Self {
+        Self {
+            size,
+            free: Vec::from([Zone32::new(Position32::new(0, 0), size)]),
+            slots: Vec::new(),
+            vacant: Vec::new(),
+        }
+    }
+
+    /// Returns the size of the atlas.
+    pub const fn size(&self) -> Size32 {
+        self.size
+    }
+
+    /// Allocates a rectangle of the given `size`, if there's room for it.
+    pub fn allocate(&mut self, size: Size32) -> Option<(AllocId, Zone32)> {
+        let (w, h) = size.as_tuple();
+        let r = self
+            .free
+            .iter()
+            .filter(|r| w <= r.w() && h <= r.h())
+            .min_by_key(|r| (area(r) - w as u64 * h as u64, r.x(), r.y()))?;
+        let zone = Zone32::new(r.position(), size);
+        self.occupy(zone);
+
+        let id = match self.vacant.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.zone = Some(zone);
+                AllocId {
+                    index,
+                    generation: slot.generation,
+                }
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    zone: Some(zone),
+                });
+                AllocId {
+                    index: self.slots.len() as u32 - 1,
+                    generation: 0,
+                }
+            }
+        };
+        Some((id, zone))
+    }
+
+    /// Deallocates the rectangle with the given `id`, and returns it.
+    ///
+    /// Returns `None` if the `id` was already deallocated.
+    pub fn deallocate(&mut self, id: AllocId) -> Option<Zone32> {
+        let slot = self.slots.get_mut(id.index as usize)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        let zone = slot.zone.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.vacant.push(id.index);
+        self.release(zone);
+        Some(zone)
+    }
+
+    /// Returns the rectangle allocated with the given `id`, if it's still allocated.
+    pub fn get(&self, id: AllocId) -> Option<Zone32> {
+        self.slots
+            .get(id.index as usize)
+            .filter(|slot| slot.generation == id.generation)
+            .and_then(|slot| slot.zone)
+    }
+
+    /// Grows the atlas to the given `size`, keeping all the existing
+    /// allocations in place.
+    ///
+    /// Each dimension only changes if the new one is greater.
+    pub fn grow(&mut self, size: Size32) {
+        let (old_w, old_h) = self.size.as_tuple();
+        let (w, h) = (size.w().max(old_w), size.h().max(old_h));
+        self.size = Size32::new(w, h);
+        if h > old_h {
+            self.release(Zone32::new_raw(0, old_h, old_w, h - old_h));
+        }
+        if w > old_w {
+            self.release(Zone32::new_raw(old_w, 0, w - old_w, h));
+        }
+    }
+
+    /// Returns the current statistics.
+    pub fn stats(&self) -> AtlasStats {
+        AtlasStats {
+            allocations: self.allocations().count(),
+            free_area: area(&Zone32::new(Position32::new(0, 0), self.size))
+                - self.allocations().map(|z| area(&z)).sum::<u64>(),
+            largest_free: self.free.iter().copied().max_by_key(area),
+        }
+    }
+
+    /// Returns the live allocations.
+    fn allocations(&self) -> impl Iterator<Item = Zone32> + '_ {
+        self.slots.iter().filter_map(|slot| slot.zone)
+    }
+
+    /// Removes the `used` zone from the free rectangles, keeping them maximal.
+    fn occupy(&mut self, used: Zone32) {
+        // split every free rectangle overlapping the zone into maximal ones
+        let mut split = Vec::with_capacity(8);
+        self.free.retain(|r| {
+            if !r.overlaps(used) {
+                return true;
+            }
+            if used.x() > r.x() {
+                split.push(Zone32::new_raw(r.x(), r.y(), used.x() - r.x(), r.h()));
+            }
+            if used.x_end() < r.x_end() {
+                let w = r.x_end() - used.x_end();
+                split.push(Zone32::new_raw(used.x_end(), r.y(), w, r.h()));
+            }
+            if used.y() > r.y() {
+                split.push(Zone32::new_raw(r.x(), r.y(), r.w(), used.y() - r.y()));
+            }
+            if used.y_end() < r.y_end() {
+                let h = r.y_end() - used.y_end();
+                split.push(Zone32::new_raw(r.x(), used.y_end(), r.w(), h));
+            }
+            false
+        });
+        let start = self.free.len();
+        self.free.extend(split);
+        prune_contained(&mut self.free, start, contains);
+    }
+
+    /// Adds a free `zone`, and the maximal rectangles spanning it and its free
+    /// neighbors, keeping them maximal.
+    fn release(&mut self, zone: Zone32) {
+        let start = self.free.len();
+        self.free.push(zone);
+
+        // stretch each new rectangle over the free ones it touches
+        let mut next = start;
+        while next < self.free.len() {
+            let r = self.free[next];
+            next += 1;
+            for f in 0..self.free.len() {
+                for stretched in stretch(r, self.free[f]).into_iter().flatten() {
+                    if !self.free.iter().any(|o| o.contains_zone(stretched)) {
+                        self.free.push(stretched);
+                    }
+                }
+            }
+        }
+
+        // drop the old ones contained in a new one
+        let added = self.free.split_off(start);
+        self.free
+            .retain(|r| !added.iter().any(|a| a.contains_zone(*r)));
+        let start = self.free.len();
+        self.free.extend(added);
+        prune_contained(&mut self.free, start, contains);
+    }
+}
+
+/// Returns the rectangles spanning the free rectangles `a` and `b` horizontally
+/// and vertically, if they touch or overlap along that axis.
+fn stretch(a: Zone32, b: Zone32) -> [Option<Zone32>; 2] {
+    let (x, x_end) = (a.x().max(b.x()), a.x_end().min(b.x_end()));
+    let (y, y_end) = (a.y().max(b.y()), a.y_end().min(b.y_end()));
+    let horizontal = (x <= x_end && y < y_end).then(|| {
+        let x = a.x().min(b.x());
+        Zone32::new_raw(x, y, a.x_end().max(b.x_end()) - x, y_end - y)
+    });
+    let vertical = (y <= y_end && x < x_end).then(|| {
+        let y = a.y().min(b.y());
+        Zone32::new_raw(x, y, x_end - x, a.y_end().max(b.y_end()) - y)
+    });
+    [horizontal, vertical]
+}
+
+fn contains(a: &Zone32, b: &Zone32) -> bool {
+    a.contains_zone(*b)
+}
+
+fn area(zone: &Zone32) -> u64 {
+    zone.w() as u64 * zone.h() as u64
+}
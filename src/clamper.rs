@@ -1,6 +1,6 @@
 // cuadra::clamper
 //
-//!
+//! Clamping and saturating casting between primitives.
 //
 
 #![allow(dead_code)]
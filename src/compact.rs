@@ -0,0 +1,96 @@
+// cuadra::compact
+//
+//! Serde support in compact tuple form.
+//!
+//! By default the types are serialized in struct form, like `{ x: 1, y: 2 }`.
+//! This module can be used with `#[serde(with = "cuadra::compact")]` to use
+//! instead a tuple form, like `(1, 2)` for a position or a size, and
+//! `(x, y, w, h)` for a zone.
+//!
+//! ```
+//! # use cuadra::{Position16, Zone16};
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Window {
+//!     #[serde(with = "cuadra::compact")]
+//!     zone: Zone16,
+//!     #[serde(with = "cuadra::compact")]
+//!     cursor: Position16,
+//! }
+//! ```
+//
+
+use crate::{Coord, Position, Size, Zone};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// A type with a compact tuple form.
+///
+/// It's sealed, and implemented for [`Position`], [`Size`] and [`Zone`].
+pub trait Compact: Sized + private::Sealed {
+    /// The tuple form.
+    type Tuple;
+
+    /// Returns the tuple form.
+    fn to_compact(&self) -> Self::Tuple;
+    /// Returns a new value from the tuple form, clamping it.
+    fn from_compact(tuple: Self::Tuple) -> Self;
+}
+
+impl<T: Coord> private::Sealed for Position<T> {}
+impl<T: Coord> Compact for Position<T> {
+    type Tuple = (T, T);
+
+    fn to_compact(&self) -> (T, T) {
+        (self.x(), self.y())
+    }
+    fn from_compact(tuple: (T, T)) -> Self {
+        Position::from_xy(tuple.0, tuple.1)
+    }
+}
+
+impl<T: Coord> private::Sealed for Size<T> {}
+impl<T: Coord> Compact for Size<T> {
+    type Tuple = (T, T);
+
+    fn to_compact(&self) -> (T, T) {
+        (self.w(), self.h())
+    }
+    fn from_compact(tuple: (T, T)) -> Self {
+        Size::from_wh(tuple.0, tuple.1)
+    }
+}
+
+impl<T: Coord> private::Sealed for Zone<T> {}
+impl<T: Coord> Compact for Zone<T> {
+    type Tuple = (T, T, T, T);
+
+    fn to_compact(&self) -> (T, T, T, T) {
+        (self.x(), self.y(), self.w(), self.h())
+    }
+    fn from_compact(tuple: (T, T, T, T)) -> Self {
+        Zone::from_xywh(tuple.0, tuple.1, tuple.2, tuple.3)
+    }
+}
+
+/// Serializes the `value` in tuple form.
+pub fn serialize<V, S>(value: &V, serializer: S) -> Result<S::Ok, S::Error>
+where
+    V: Compact,
+    V::Tuple: Serialize,
+    S: Serializer,
+{
+    value.to_compact().serialize(serializer)
+}
+
+/// Deserializes a value from its tuple form, clamping it.
+pub fn deserialize<'de, V, D>(deserializer: D) -> Result<V, D::Error>
+where
+    V: Compact,
+    V::Tuple: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    V::Tuple::deserialize(deserializer).map(V::from_compact)
+}
@@ -0,0 +1,174 @@
+// cuadra::convert
+//
+//! Conversions between bit sizes.
+//
+// Widening is always lossless, since the clamped range of a smaller bit size
+// is always inside the clamped range of a bigger one.
+
+use crate::{Coord, Position, Size, Zone};
+use core::fmt;
+
+/// The error returned when converting to a smaller bit size whose clamped
+/// range can't represent a value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct OutOfRangeError;
+
+impl fmt::Display for OutOfRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value out of the clamped range of the target type")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OutOfRangeError {}
+
+/// Conversion that clamps the value when it's out of the range of `Self`.
+///
+/// It's implemented for [`Position`], [`Size`] and [`Zone`] between all
+/// the bit sizes. Widening never needs clamping.
+pub trait ClampFrom<T>: Sized {
+    /// Converts the `value`, clamping it to the range of `Self`.
+    fn clamp_from(value: T) -> Self;
+}
+
+/// Conversion that clamps the value when it's out of the range of `T`.
+///
+/// It's the reciprocal of [`ClampFrom`], and it's implemented for any type
+/// for which the latter is implemented.
+pub trait ClampInto<T> {
+    /// Converts the value, clamping it to the range of `T`.
+    fn clamp_into(self) -> T;
+}
+
+impl<T, U: ClampFrom<T>> ClampInto<U> for T {
+    #[inline]
+    fn clamp_into(self) -> U {
+        U::clamp_from(self)
+    }
+}
+
+/// Returns the `value` if it's inside the clamped range of `S`.
+fn narrow<S: Coord + TryFrom<i64>>(value: impl Into<i64>) -> Result<S, OutOfRangeError> {
+    match S::try_from(value.into()) {
+        Ok(v) if v.clamp_coord() == v => Ok(v),
+        _ => Err(OutOfRangeError),
+    }
+}
+
+/// Returns the `value` clamped to the clamped range of `S`.
+fn clamp<S: Coord + TryFrom<i64>>(value: impl Into<i64>) -> S {
+    let value = value.into();
+    match S::try_from(value) {
+        Ok(v) => v.clamp_coord(),
+        Err(_) if value < 0 => S::CLAMP_MIN,
+        Err(_) => S::CLAMP_MAX,
+    }
+}
+
+impl<T: Coord> ClampFrom<Position<T>> for Position<T> {
+    #[inline]
+    fn clamp_from(value: Position<T>) -> Self {
+        value
+    }
+}
+impl<T: Coord> ClampFrom<Size<T>> for Size<T> {
+    #[inline]
+    fn clamp_from(value: Size<T>) -> Self {
+        value
+    }
+}
+impl<T: Coord> ClampFrom<Zone<T>> for Zone<T> {
+    #[inline]
+    fn clamp_from(value: Zone<T>) -> Self {
+        value
+    }
+}
+
+macro_rules! convert {
+    // $s: smaller inner primitive
+    // $w: wider inner primitive
+    ( $($s:ty, $w:ty);+ ) => {
+        $( convert![single: $s, $w]; )+
+    };
+
+    (single: $s:ty, $w:ty) => {
+        /* position */
+
+        impl From<Position<$s>> for Position<$w> {
+            fn from(p: Position<$s>) -> Position<$w> {
+                Position::from_xy(p.x().into(), p.y().into())
+            }
+        }
+        impl TryFrom<Position<$w>> for Position<$s> {
+            type Error = OutOfRangeError;
+
+            fn try_from(p: Position<$w>) -> Result<Position<$s>, OutOfRangeError> {
+                Ok(Position::from_xy(narrow(p.x())?, narrow(p.y())?))
+            }
+        }
+        impl ClampFrom<Position<$s>> for Position<$w> {
+            #[inline]
+            fn clamp_from(p: Position<$s>) -> Position<$w> {
+                p.into()
+            }
+        }
+        impl ClampFrom<Position<$w>> for Position<$s> {
+            fn clamp_from(p: Position<$w>) -> Position<$s> {
+                Position::from_xy(clamp(p.x()), clamp(p.y()))
+            }
+        }
+
+        /* size */
+
+        impl From<Size<$s>> for Size<$w> {
+            fn from(s: Size<$s>) -> Size<$w> {
+                Size::from_wh(s.w().into(), s.h().into())
+            }
+        }
+        impl TryFrom<Size<$w>> for Size<$s> {
+            type Error = OutOfRangeError;
+
+            fn try_from(s: Size<$w>) -> Result<Size<$s>, OutOfRangeError> {
+                Ok(Size::from_wh(narrow(s.w())?, narrow(s.h())?))
+            }
+        }
+        impl ClampFrom<Size<$s>> for Size<$w> {
+            #[inline]
+            fn clamp_from(s: Size<$s>) -> Size<$w> {
+                s.into()
+            }
+        }
+        impl ClampFrom<Size<$w>> for Size<$s> {
+            fn clamp_from(s: Size<$w>) -> Size<$s> {
+                Size::from_wh(clamp(s.w()), clamp(s.h()))
+            }
+        }
+
+        /* zone */
+
+        impl From<Zone<$s>> for Zone<$w> {
+            fn from(z: Zone<$s>) -> Zone<$w> {
+                Zone { p: z.p.into(), s: z.s.into() }
+            }
+        }
+        impl TryFrom<Zone<$w>> for Zone<$s> {
+            type Error = OutOfRangeError;
+
+            fn try_from(z: Zone<$w>) -> Result<Zone<$s>, OutOfRangeError> {
+                Ok(Zone { p: z.p.try_into()?, s: z.s.try_into()? })
+            }
+        }
+        impl ClampFrom<Zone<$s>> for Zone<$w> {
+            #[inline]
+            fn clamp_from(z: Zone<$s>) -> Zone<$w> {
+                z.into()
+            }
+        }
+        impl ClampFrom<Zone<$w>> for Zone<$s> {
+            fn clamp_from(z: Zone<$w>) -> Zone<$s> {
+                Zone { p: z.p.clamp_into(), s: z.s.clamp_into() }
+            }
+        }
+    };
+}
+convert![i8, i16; i8, i32; i8, i64; i16, i32; i16, i64; i32, i64];
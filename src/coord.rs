@@ -0,0 +1,128 @@
+// cuadra::coord
+//
+//! The primitives used as coordinates.
+//
+
+use crate::{Clamper16, Clamper32, Clamper64, Clamper8};
+use core::{fmt, hash::Hash};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// A primitive usable as the inner type of a [`Position`], a [`Size`] and a [`Zone`].
+///
+/// It allows to write code generic over the bit size. It's sealed, and
+/// implemented for [`i8`], [`i16`], [`i32`] and [`i64`], each one clamped
+/// by its corresponding clamper, like [`Clamper8`] for [`i8`].
+///
+/// [`Position`]: crate::Position
+/// [`Size`]: crate::Size
+/// [`Zone`]: crate::Zone
+pub trait Coord: Copy + Default + Ord + Hash + fmt::Debug + fmt::Display + private::Sealed {
+    /// The bit size.
+    const BITS: u32;
+
+    /// The minimum clamped value, the same as the clamper's `MIN`.
+    const CLAMP_MIN: Self;
+    /// The maximum clamped value, the same as the clamper's `MAX`.
+    const CLAMP_MAX: Self;
+
+    /// The value `0`.
+    const ZERO: Self;
+    /// The value `1`.
+    const ONE: Self;
+
+    /// Clamps the value to [`CLAMP_MIN`][Self::CLAMP_MIN]`..`[`CLAMP_MAX`][Self::CLAMP_MAX].
+    fn clamp_coord(self) -> Self;
+    /// Clamps the value to `0..`[`CLAMP_MAX`][Self::CLAMP_MAX].
+    fn clamp_non_negative(self) -> Self;
+    /// Clamps the value to `1..`[`CLAMP_MAX`][Self::CLAMP_MAX].
+    fn clamp_positive(self) -> Self;
+
+    /// Saturating addition, unclamped.
+    fn saturating_add(self, rhs: Self) -> Self;
+    /// Saturating substraction, unclamped.
+    fn saturating_sub(self, rhs: Self) -> Self;
+    /// Saturating multiplication, unclamped.
+    fn saturating_mul(self, rhs: Self) -> Self;
+    /// Saturating division, unclamped.
+    fn saturating_div(self, rhs: Self) -> Self;
+
+    /// Checked addition, unclamped.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    /// Checked substraction, unclamped.
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    /// Checked multiplication, unclamped.
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    /// Checked division, unclamped.
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! coord {
+    // $i: inner primitive
+    // $b: bit size
+    ( $($i:ty, $b:expr),+ ) => {
+        $( coord![single: $i, $b]; )+
+    };
+
+    (single: $i:ty, $b:literal) => { paste::paste! {
+        impl private::Sealed for $i {}
+
+        impl Coord for $i {
+            const BITS: u32 = $b;
+            const CLAMP_MIN: $i = [<Clamper$b>]::MIN;
+            const CLAMP_MAX: $i = [<Clamper$b>]::MAX;
+            const ZERO: $i = 0;
+            const ONE: $i = 1;
+
+            #[inline]
+            fn clamp_coord(self) -> $i {
+                [<Clamper$b>]::clamp(self)
+            }
+            #[inline]
+            fn clamp_non_negative(self) -> $i {
+                [<Clamper$b>]::clamp_non_negative(self)
+            }
+            #[inline]
+            fn clamp_positive(self) -> $i {
+                [<Clamper$b>]::clamp_positive(self)
+            }
+
+            #[inline]
+            fn saturating_add(self, rhs: $i) -> $i {
+                <$i>::saturating_add(self, rhs)
+            }
+            #[inline]
+            fn saturating_sub(self, rhs: $i) -> $i {
+                <$i>::saturating_sub(self, rhs)
+            }
+            #[inline]
+            fn saturating_mul(self, rhs: $i) -> $i {
+                <$i>::saturating_mul(self, rhs)
+            }
+            #[inline]
+            fn saturating_div(self, rhs: $i) -> $i {
+                <$i>::saturating_div(self, rhs)
+            }
+
+            #[inline]
+            fn checked_add(self, rhs: $i) -> Option<$i> {
+                <$i>::checked_add(self, rhs)
+            }
+            #[inline]
+            fn checked_sub(self, rhs: $i) -> Option<$i> {
+                <$i>::checked_sub(self, rhs)
+            }
+            #[inline]
+            fn checked_mul(self, rhs: $i) -> Option<$i> {
+                <$i>::checked_mul(self, rhs)
+            }
+            #[inline]
+            fn checked_div(self, rhs: $i) -> Option<$i> {
+                <$i>::checked_div(self, rhs)
+            }
+        }
+    }};
+}
+coord![i8, 8, i16, 16, i32, 32, i64, 64];
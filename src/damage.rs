@@ -0,0 +1,187 @@
+// cuadra::damage
+//
+//! Damage tracking.
+//
+
+use crate::{Position16, Size16, Zone16};
+use alloc::vec::Vec;
+
+/// Accumulates the dirty zones of a screen between frames.
+///
+/// The tracked zones are always clipped to the screen and never overlap,
+/// so no cell is repainted twice. A new zone touching a tracked one is
+/// merged with it when their bounding box wastes at most the maximum
+/// wasted area, and doesn't overlap any other tracked zone. When there
+/// are more zones than the maximum count, the pair that wastes the least
+/// area is merged, until there are few enough.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DamageTracker {
+    screen: Size16,
+    rects: Vec<Zone16>,
+    max_rects: usize,
+    max_waste: u32,
+}
+
+impl DamageTracker {
+    /// Returns a new tracker for a screen of the given size,
+    /// with a maximum of `16` zones and a maximum wasted area of `0`.
+    pub const fn new(screen: Size16) -> Self {
+        Self {
+            screen,
+            rects: Vec::new(),
+            max_rects: 16,
+            max_waste: 0,
+        }
+    }
+
+    /// Chain-sets the maximum number of zones, which is at least `1`.
+    pub const fn max_rects(mut self, max_rects: usize) -> Self {
+        self.max_rects = if max_rects > 1 { max_rects } else { 1 };
+        self
+    }
+
+    /// Chain-sets the maximum area that merging two touching zones
+    /// can add to the repaint list, without having to.
+    pub const fn max_waste(mut self, max_waste: u32) -> Self {
+        self.max_waste = max_waste;
+        self
+    }
+
+    /// Returns the size of the screen.
+    pub const fn screen(&self) -> Size16 {
+        self.screen
+    }
+
+    /// Resizes the screen, clipping the tracked zones to the new size.
+    pub fn resize(&mut self, screen: Size16) {
+        self.screen = screen;
+        let bounds = self.bounds();
+        self.rects = self
+            .rects
+            .iter()
+            .filter_map(|r| r.intersection(bounds))
+            .collect();
+    }
+
+    /// Returns the tracked zones, in no particular order.
+    pub fn rects(&self) -> &[Zone16] {
+        &self.rects
+    }
+
+    /// Returns `true` if nothing needs to be repainted.
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// Returns the total area to repaint.
+    pub fn area(&self) -> u32 {
+        self.rects.iter().map(area).sum()
+    }
+
+    /// Marks the whole screen as dirty.
+    pub fn add_screen(&mut self) {
+        self.rects.clear();
+        self.rects.push(self.bounds());
+    }
+
+    /// Marks the given `zone` as dirty.
+    pub fn add(&mut self, zone: Zone16) {
+        let mut pending = match zone.intersection(self.bounds()) {
+            Some(zone) => Vec::from([zone]),
+            None => return,
+        };
+        'pending: while let Some(mut zone) = pending.pop() {
+            let mut i = 0;
+            while i < self.rects.len() {
+                let r = self.rects[i];
+                if r.contains_zone(zone) {
+                    continue 'pending;
+                } else if zone.contains_zone(r) {
+                    self.rects.swap_remove(i);
+                    continue;
+                } else if touches(zone, r) {
+                    let merged = zone.union(r);
+                    // a merged zone overlapping another one would be split again
+                    let overlaps_other = self
+                        .rects
+                        .iter()
+                        .enumerate()
+                        .any(|(j, o)| j != i && o.overlaps(merged));
+                    if waste(merged, zone, r) <= self.max_waste && !overlaps_other {
+                        self.rects.swap_remove(i);
+                        zone = merged;
+                        // the merged zone can reach the previous ones
+                        i = 0;
+                        continue;
+                    } else if zone.overlaps(r) {
+                        pending.extend(zone.difference(r));
+                        continue 'pending;
+                    }
+                }
+                i += 1;
+            }
+            self.rects.push(zone);
+        }
+        self.limit();
+    }
+
+    /// Returns the zones to repaint, sorted from top to bottom and from
+    /// left to right, and clears the tracker.
+    pub fn take(&mut self) -> Vec<Zone16> {
+        let mut rects = core::mem::take(&mut self.rects);
+        rects.sort_by_key(|r| (r.y(), r.x()));
+        rects
+    }
+
+    /// Clears the tracker without returning the zones.
+    pub fn clear(&mut self) {
+        self.rects.clear();
+    }
+
+    /* private helpers */
+
+    fn bounds(&self) -> Zone16 {
+        Zone16::new(Position16::new(0, 0), self.screen)
+    }
+
+    /// Merges the pairs of zones that waste the least area
+    /// until there are at most the maximum number of zones.
+    fn limit(&mut self) {
+        while self.rects.len() > self.max_rects {
+            let mut best: Option<(u32, usize, usize)> = None;
+            for i in 0..self.rects.len() {
+                for j in i + 1..self.rects.len() {
+                    let (a, b) = (self.rects[i], self.rects[j]);
+                    let w = waste(a.union(b), a, b);
+                    if best.map_or(true, |(bw, _, _)| w < bw) {
+                        best = Some((w, i, j));
+                    }
+                }
+            }
+            let (_, i, j) = best.expect("more than one zone");
+            let mut merged = self.rects[i].union(self.rects[j]);
+            self.rects.swap_remove(j);
+            self.rects.swap_remove(i);
+            // absorb any zone the merged one now overlaps, to stay disjoint
+            while let Some(o) = self.rects.iter().position(|r| r.overlaps(merged)) {
+                merged = merged.union(self.rects.swap_remove(o));
+            }
+            self.rects.push(merged);
+        }
+    }
+}
+
+fn area(zone: &Zone16) -> u32 {
+    zone.w() as u32 * zone.h() as u32
+}
+
+/// Returns `true` if the zones overlap or share part of an edge or corner.
+fn touches(a: Zone16, b: Zone16) -> bool {
+    a.x() <= b.x_end() && b.x() <= a.x_end() && a.y() <= b.y_end() && b.y() <= a.y_end()
+}
+
+/// Returns the area of `merged` not covered by either `a` or `b`.
+fn waste(merged: Zone16, a: Zone16, b: Zone16) -> u32 {
+    let shared = a.intersection(b).as_ref().map_or(0, area);
+    area(&merged) + shared - area(&a) - area(&b)
+}
@@ -0,0 +1,242 @@
+// cuadra::direction
+//
+//! Directions and neighborhoods.
+//
+
+use crate::{Coord, Offset, Position, Zone};
+use core::iter::FusedIterator;
+
+/// One of the four orthogonal directions.
+///
+/// Up is towards the lower `y` values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Direction4 {
+    #[default]
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+/// One of the four orthogonal and four diagonal directions.
+///
+/// Up is towards the lower `y` values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    #[default]
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction4 {
+    /// All the directions, clockwise from [`Up`][Self::Up].
+    pub const ALL: [Direction4; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Returns the next direction clockwise.
+    pub const fn cw(&self) -> Direction4 {
+        Self::ALL[(*self as usize + 1) % 4]
+    }
+    /// Returns the next direction counterclockwise.
+    pub const fn ccw(&self) -> Direction4 {
+        Self::ALL[(*self as usize + 3) % 4]
+    }
+    /// Returns the opposite direction.
+    pub const fn opposite(&self) -> Direction4 {
+        Self::ALL[(*self as usize + 2) % 4]
+    }
+
+    /// Returns the equivalent [`Direction8`].
+    pub const fn to_direction8(&self) -> Direction8 {
+        Direction8::ALL[*self as usize * 2]
+    }
+
+    /// Returns the horizontal and vertical unit steps.
+    pub const fn deltas(&self) -> (i8, i8) {
+        self.to_direction8().deltas()
+    }
+    /// Returns the offset of a single step in this direction.
+    pub fn offset<T: Coord>(&self) -> Offset<T> {
+        self.to_direction8().offset()
+    }
+    /// Returns the position a single step away from the origin in this direction.
+    pub fn position<T: Coord>(&self) -> Position<T> {
+        self.to_direction8().position()
+    }
+}
+
+impl Direction8 {
+    /// All the directions, clockwise from [`Up`][Self::Up].
+    pub const ALL: [Direction8; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// Returns the next direction clockwise, an eighth of a turn away.
+    pub const fn cw(&self) -> Direction8 {
+        Self::ALL[(*self as usize + 1) % 8]
+    }
+    /// Returns the next direction counterclockwise, an eighth of a turn away.
+    pub const fn ccw(&self) -> Direction8 {
+        Self::ALL[(*self as usize + 7) % 8]
+    }
+    /// Returns the opposite direction.
+    pub const fn opposite(&self) -> Direction8 {
+        Self::ALL[(*self as usize + 4) % 8]
+    }
+
+    /// Returns `true` if the direction is diagonal.
+    pub const fn is_diagonal(&self) -> bool {
+        *self as usize % 2 == 1
+    }
+    /// Returns the equivalent [`Direction4`], or `None` if it's diagonal.
+    pub const fn to_direction4(&self) -> Option<Direction4> {
+        if self.is_diagonal() {
+            None
+        } else {
+            Some(Direction4::ALL[*self as usize / 2])
+        }
+    }
+
+    /// Returns the horizontal and vertical unit steps.
+    pub const fn deltas(&self) -> (i8, i8) {
+        match self {
+            Self::Up => (0, -1),
+            Self::UpRight => (1, -1),
+            Self::Right => (1, 0),
+            Self::DownRight => (1, 1),
+            Self::Down => (0, 1),
+            Self::DownLeft => (-1, 1),
+            Self::Left => (-1, 0),
+            Self::UpLeft => (-1, -1),
+        }
+    }
+    /// Returns the offset of a single step in this direction.
+    pub fn offset<T: Coord>(&self) -> Offset<T> {
+        let (dx, dy) = self.deltas();
+        Offset::new(unit(dx), unit(dy))
+    }
+    /// Returns the position a single step away from the origin in this direction.
+    pub fn position<T: Coord>(&self) -> Position<T> {
+        let (dx, dy) = self.deltas();
+        Position::from_xy(unit(dx), unit(dy))
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(d: Direction4) -> Direction8 {
+        d.to_direction8()
+    }
+}
+
+/// Returns the unit step `d`, which is `-1`, `0` or `1`.
+fn unit<T: Coord>(d: i8) -> T {
+    match d {
+        0 => T::ZERO,
+        1 => T::ONE,
+        _ => T::ZERO.saturating_sub(T::ONE),
+    }
+}
+
+/// An iterator over the neighbors of a [`Position`], clockwise from the one above.
+///
+/// Neighbors beyond the clamped range are skipped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Neighbors<T: Coord> {
+    position: Position<T>,
+    bound: Option<Zone<T>>,
+    /// The index of the next direction in [`Direction8::ALL`].
+    next: usize,
+    /// `1` for the 8-neighborhood and `2` for the 4-neighborhood.
+    step: usize,
+}
+
+impl<T: Coord> Neighbors<T> {
+    /// Chain-sets the zone the neighbors must be inside of.
+    pub fn within(mut self, zone: Zone<T>) -> Self {
+        self.bound = Some(zone);
+        self
+    }
+
+    /// Returns the neighbor in the given `direction`, if it's representable
+    /// and inside the bound.
+    fn neighbor(&self, direction: Direction8) -> Option<Position<T>> {
+        let (dx, dy) = direction.deltas();
+        let step = |v: T, d: i8| {
+            let v = v.checked_add(unit(d))?;
+            (v.clamp_coord() == v).then_some(v)
+        };
+        let (x, y) = (step(self.position.x(), dx)?, step(self.position.y(), dy)?);
+        match self.bound {
+            Some(z) if x < z.x() || x >= z.x().saturating_add(z.w()) => None,
+            Some(z) if y < z.y() || y >= z.y().saturating_add(z.h()) => None,
+            _ => Some(Position::from_xy(x, y)),
+        }
+    }
+}
+
+impl<T: Coord> Iterator for Neighbors<T> {
+    type Item = Position<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next < Direction8::ALL.len() {
+            let direction = Direction8::ALL[self.next];
+            self.next += self.step;
+            if let Some(position) = self.neighbor(direction) {
+                return Some(position);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (
+            0,
+            Some((Direction8::ALL.len() - self.next + self.step - 1) / self.step),
+        )
+    }
+}
+
+impl<T: Coord> FusedIterator for Neighbors<T> {}
+
+/// # neighbors
+impl<T: Coord> Position<T> {
+    /// Returns an iterator over the 4 orthogonal neighbors,
+    /// clockwise from the one above.
+    pub const fn neighbors4(&self) -> Neighbors<T> {
+        Neighbors {
+            position: *self,
+            bound: None,
+            next: 0,
+            step: 2,
+        }
+    }
+    /// Returns an iterator over the 8 orthogonal and diagonal neighbors,
+    /// clockwise from the one above.
+    pub const fn neighbors8(&self) -> Neighbors<T> {
+        Neighbors {
+            position: *self,
+            bound: None,
+            next: 0,
+            step: 1,
+        }
+    }
+
+    /// Returns the neighbor position in the given `direction`.
+    ///
+    /// The result is clamped.
+    pub fn step(&self, direction: impl Into<Direction8>) -> Position<T> {
+        *self + direction.into().offset()
+    }
+}
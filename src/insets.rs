@@ -0,0 +1,224 @@
+// cuadra::insets
+//
+//! 2D insets.
+//
+
+use core::{
+    fmt,
+    ops::{Add, Sub},
+};
+
+macro_rules! insets {
+    // $i: inner primitive
+    // $b: bit size
+    ( $($i:ty, $b:expr),+ ) => {
+        $( insets![single: $i, $b]; )+
+    };
+
+    (single: $i:ty, $b:literal) => { paste::paste! {
+        use super::{[<Clamper$b>] as [<C$b>], [<Size$b>], [<Zone$b>]};
+
+        #[doc = "The insets at each side of a zone, using a non-negative clamped [`" $i "`]."]
+        ///
+        /// They can represent margins, paddings or border widths.
+        /// The top side is the one towards the lower `y` values.
+        #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+        pub struct [<Insets$b>] {
+            top: $i,
+            right: $i,
+            bottom: $i,
+            left: $i,
+        }
+
+        impl fmt::Debug for [<Insets$b>] {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(
+                    f,
+                    "{} {{ top: {}, right: {}, bottom: {}, left: {} }}",
+                    stringify!([<Insets$b>]),
+                    self.top,
+                    self.right,
+                    self.bottom,
+                    self.left,
+                )
+            }
+        }
+
+        impl fmt::Display for [<Insets$b>] {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "t:{} r:{} b:{} l:{}", self.top, self.right, self.bottom, self.left)
+            }
+        }
+
+        impl [<Insets$b>] {
+            /// Defines new `Insets` with the given sides, in clockwise order,
+            /// which can't be negative.
+            pub const fn new(top: $i, right: $i, bottom: $i, left: $i) -> Self {
+                Self {
+                    top: [<C$b>]::clamp_non_negative(top),
+                    right: [<C$b>]::clamp_non_negative(right),
+                    bottom: [<C$b>]::clamp_non_negative(bottom),
+                    left: [<C$b>]::clamp_non_negative(left),
+                }
+            }
+            /// Defines new `Insets` with the same `value` for all sides.
+            pub const fn uniform(value: $i) -> Self {
+                Self::new(value, value, value, value)
+            }
+            /// Defines new `Insets` with the same `vertical` value for the top
+            /// and bottom sides, and the same `horizontal` value for the others.
+            pub const fn symmetric(vertical: $i, horizontal: $i) -> Self {
+                Self::new(vertical, horizontal, vertical, horizontal)
+            }
+
+            /// Get the top inset.
+            #[inline]
+            pub const fn top(&self) -> $i {
+                self.top
+            }
+            /// Get the right inset.
+            #[inline]
+            pub const fn right(&self) -> $i {
+                self.right
+            }
+            /// Get the bottom inset.
+            #[inline]
+            pub const fn bottom(&self) -> $i {
+                self.bottom
+            }
+            /// Get the left inset.
+            #[inline]
+            pub const fn left(&self) -> $i {
+                self.left
+            }
+
+            /// Set the top inset.
+            #[inline]
+            pub fn set_top(&mut self, top: $i) {
+                self.top = [<C$b>]::clamp_non_negative(top);
+            }
+            /// Set the right inset.
+            #[inline]
+            pub fn set_right(&mut self, right: $i) {
+                self.right = [<C$b>]::clamp_non_negative(right);
+            }
+            /// Set the bottom inset.
+            #[inline]
+            pub fn set_bottom(&mut self, bottom: $i) {
+                self.bottom = [<C$b>]::clamp_non_negative(bottom);
+            }
+            /// Set the left inset.
+            #[inline]
+            pub fn set_left(&mut self, left: $i) {
+                self.left = [<C$b>]::clamp_non_negative(left);
+            }
+
+            /// Returns the sum of the left and right insets.
+            ///
+            /// It's always representable thanks to the clamped safety margin.
+            #[inline]
+            pub const fn horizontal(&self) -> $i {
+                self.left + self.right
+            }
+            /// Returns the sum of the top and bottom insets.
+            ///
+            /// It's always representable thanks to the clamped safety margin.
+            #[inline]
+            pub const fn vertical(&self) -> $i {
+                self.top + self.bottom
+            }
+        }
+
+        /// # arithmetic ops.
+        impl [<Insets$b>] {
+            #[doc = "Saturating, clamped addition of two `" [<Insets$b>] "`."]
+            pub const fn saturating_add(&self, rhs: [<Insets$b>]) -> [<Insets$b>] {
+                Self::new(
+                    self.top.saturating_add(rhs.top),
+                    self.right.saturating_add(rhs.right),
+                    self.bottom.saturating_add(rhs.bottom),
+                    self.left.saturating_add(rhs.left),
+                    )
+            }
+            #[doc = "Saturating, clamped substraction of two `" [<Insets$b>] "`."]
+            pub const fn saturating_sub(&self, rhs: [<Insets$b>]) -> [<Insets$b>] {
+                Self::new(
+                    self.top.saturating_sub(rhs.top),
+                    self.right.saturating_sub(rhs.right),
+                    self.bottom.saturating_sub(rhs.bottom),
+                    self.left.saturating_sub(rhs.left),
+                    )
+            }
+            #[doc = "Saturating, clamped multiplication of a `" [<Insets$b>] " with a `value``."]
+            pub const fn saturating_mul_value(&self, value: $i) -> [<Insets$b>] {
+                Self::new(
+                    self.top.saturating_mul(value),
+                    self.right.saturating_mul(value),
+                    self.bottom.saturating_mul(value),
+                    self.left.saturating_mul(value),
+                    )
+            }
+        }
+
+        impl Add for [<Insets$b>] {
+            type Output = Self;
+
+            /// Saturating, clamped addition.
+            #[inline]
+            fn add(self, rhs: Self) -> Self {
+                self.saturating_add(rhs)
+            }
+        }
+        impl Sub for [<Insets$b>] {
+            type Output = Self;
+
+            /// Saturating, clamped substraction.
+            #[inline]
+            fn sub(self, rhs: Self) -> Self {
+                self.saturating_sub(rhs)
+            }
+        }
+
+        /// # insets
+        impl [<Zone$b>] {
+            /// Returns the zone shrunk by the given `insets` at each side.
+            ///
+            /// The size is clamped to stay at least `1`, and the left and top
+            /// insets to the zone's extent, so that the result stays inside of it.
+            pub const fn inset(&self, insets: [<Insets$b>]) -> [<Zone$b>] {
+                let left = if insets.left < self.w() { insets.left } else { self.w() - 1 };
+                let top = if insets.top < self.h() { insets.top } else { self.h() - 1 };
+                Self::new_raw(
+                    self.x() + left,
+                    self.y() + top,
+                    self.w() - insets.horizontal(),
+                    self.h() - insets.vertical(),
+                )
+            }
+            /// Returns the zone grown by the given `insets` at each side.
+            ///
+            /// The size is clamped to stay at most the clamped maximum.
+            pub const fn outset(&self, insets: [<Insets$b>]) -> [<Zone$b>] {
+                Self::new_raw(
+                    self.x() - insets.left,
+                    self.y() - insets.top,
+                    self.w().saturating_add(insets.horizontal()),
+                    self.h().saturating_add(insets.vertical()),
+                )
+            }
+        }
+
+        /// # insets
+        impl [<Size$b>] {
+            /// Returns the size grown by the given `insets`, e.g. the outer
+            /// size of some content with padding.
+            pub const fn with_insets(&self, insets: [<Insets$b>]) -> [<Size$b>] {
+                Self::new(
+                    self.w().saturating_add(insets.horizontal()),
+                    self.h().saturating_add(insets.vertical()),
+                )
+            }
+        }
+    }};
+}
+insets![i8, 8, i16, 16, i32, 32, i64, 64];
@@ -0,0 +1,270 @@
+// cuadra::layout::flex
+//
+//! Flexbox layout.
+//
+
+use super::{distribute, zone32, Axis};
+use crate::{Clamper32, Size32, Zone32};
+use alloc::vec::Vec;
+
+/// How the leftover length of a flex line is distributed along the main axis.
+///
+/// Overflowing lines are always placed from the start.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum JustifyContent {
+    /// Items are packed at the start of the line.
+    #[default]
+    Start,
+    /// Items are packed at the end of the line.
+    End,
+    /// Items are packed at the center of the line.
+    Center,
+    /// The first and last items touch the line ends, the rest is spread between items.
+    SpaceBetween,
+    /// Each item gets the same space on both of its sides.
+    SpaceAround,
+    /// The spaces between items and around the line ends are all the same.
+    SpaceEvenly,
+}
+
+/// How flex items are aligned along the cross axis of their line.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AlignItems {
+    /// Items are aligned at the start of the line.
+    Start,
+    /// Items are aligned at the end of the line.
+    End,
+    /// Items are centered in the line.
+    Center,
+    /// Items are stretched to fill the line.
+    #[default]
+    Stretch,
+}
+
+/// An item of a [`Flex`] layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FlexItem {
+    /// The size of the item before growing or shrinking.
+    pub base: Size32,
+    /// The weight with which the item takes the free length of its line.
+    pub grow: u16,
+    /// The weight, multiplied by its base length, with which the item gives
+    /// up length when its line overflows.
+    pub shrink: u16,
+}
+
+impl FlexItem {
+    /// Returns a new item with the given `base` size, that doesn't grow, and
+    /// shrinks with a weight of `1`.
+    pub const fn new(base: Size32) -> Self {
+        Self {
+            base,
+            grow: 0,
+            shrink: 1,
+        }
+    }
+
+    /// Chain-sets the grow weight.
+    pub const fn grow(mut self, grow: u16) -> Self {
+        self.grow = grow;
+        self
+    }
+
+    /// Chain-sets the shrink weight.
+    pub const fn shrink(mut self, shrink: u16) -> Self {
+        self.shrink = shrink;
+        self
+    }
+}
+
+/// A flexbox layout, placing [`FlexItem`]s inside a [`Zone32`].
+///
+/// Items are placed in lines along the main [`Axis`]. Each line grows or
+/// shrinks its items to fit the container, distributes the free length as
+/// indicated by [`JustifyContent`], and aligns the items along the cross axis
+/// as indicated by [`AlignItems`].
+///
+/// Without wrapping there's a single line as long as the container cross axis.
+/// With wrapping, each line is as long as its longest item along the cross
+/// axis, and lines are stacked from the start of the container.
+///
+/// All the arithmetic is done with a wider primitive, and the resulting zones
+/// are clamped to the [`Clamper32`] range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Flex {
+    axis: Axis,
+    wrap: bool,
+    justify: JustifyContent,
+    align: AlignItems,
+    gap: i32,
+}
+
+impl Flex {
+    /// Returns a new flex layout along the given main `axis`, without wrapping,
+    /// nor gaps, and with the default justification and alignment.
+    pub const fn new(axis: Axis) -> Self {
+        Self {
+            axis,
+            wrap: false,
+            justify: JustifyContent::Start,
+            align: AlignItems::Stretch,
+            gap: 0,
+        }
+    }
+
+    /// Chain-sets whether items wrap into several lines.
+    pub const fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Chain-sets the justification of the items along the main axis.
+    pub const fn justify_content(mut self, justify: JustifyContent) -> Self {
+        self.justify = justify;
+        self
+    }
+
+    /// Chain-sets the alignment of the items along the cross axis.
+    pub const fn align_items(mut self, align: AlignItems) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Chain-sets the gap between items, and between lines.
+    pub const fn gap(mut self, gap: i32) -> Self {
+        self.gap = Clamper32::clamp_non_negative(gap);
+        self
+    }
+
+    /// Returns the zone of each item, placed inside the `container`.
+    pub fn layout(&self, container: Zone32, items: &[FlexItem]) -> Vec<Zone32> {
+        let (main_start, cross_start, main_len, cross_len) = match self.axis {
+            Axis::Horizontal => (container.x(), container.y(), container.w(), container.h()),
+            Axis::Vertical => (container.y(), container.x(), container.h(), container.w()),
+        };
+        let (main_len, cross_len, gap) = (main_len as i64, cross_len as i64, self.gap as i64);
+        let (base_main, base_cross): (Vec<i64>, Vec<i64>) = items
+            .iter()
+            .map(|item| match self.axis {
+                Axis::Horizontal => (item.base.w() as i64, item.base.h() as i64),
+                Axis::Vertical => (item.base.h() as i64, item.base.w() as i64),
+            })
+            .unzip();
+
+        let mut zones = Vec::with_capacity(items.len());
+        let mut line_offset = 0;
+        let mut first = 0;
+        while first < items.len() {
+            // collect the items of the line
+            let mut last = first + 1;
+            let mut used = base_main[first];
+            if self.wrap {
+                while last < items.len() && used + gap + base_main[last] <= main_len {
+                    used += gap + base_main[last];
+                    last += 1;
+                }
+            } else {
+                last = items.len();
+                used = base_main.iter().sum::<i64>() + gap * (last as i64 - 1);
+            }
+            let line = first..last;
+
+            // grow or shrink the items to fit the line
+            let mut lengths = base_main[line.clone()].to_vec();
+            let free = main_len - used;
+            if free > 0 {
+                let weights: Vec<i64> = items[line.clone()].iter().map(|i| i.grow as i64).collect();
+                distribute(&mut lengths, &weights, |_| None, free);
+            } else if free < 0 {
+                let weights: Vec<i64> = items[line.clone()]
+                    .iter()
+                    .zip(&lengths)
+                    .map(|(i, len)| i.shrink as i64 * len)
+                    .collect();
+                shrink(&mut lengths, &weights, -free);
+            }
+            let free = main_len - lengths.iter().sum::<i64>() - gap * (lengths.len() as i64 - 1);
+
+            let line_cross = if self.wrap {
+                base_cross[line.clone()].iter().copied().max().unwrap_or(1)
+            } else {
+                cross_len
+            };
+
+            // place the items
+            let n = lengths.len() as i64;
+            let free = free.max(0);
+            let mut main_offset = 0;
+            for (k, (len, cross)) in lengths.iter().zip(&base_cross[line]).enumerate() {
+                let k = k as i64;
+                let space = match self.justify {
+                    JustifyContent::Start => 0,
+                    JustifyContent::End => free,
+                    JustifyContent::Center => free / 2,
+                    JustifyContent::SpaceBetween if n > 1 => free * k / (n - 1),
+                    JustifyContent::SpaceBetween => 0,
+                    JustifyContent::SpaceAround => free * (2 * k + 1) / (2 * n),
+                    JustifyContent::SpaceEvenly => free * (k + 1) / (n + 1),
+                };
+                let (cross_offset, cross) = match self.align {
+                    AlignItems::Start => (0, *cross),
+                    AlignItems::End => (line_cross - cross, *cross),
+                    AlignItems::Center => ((line_cross - cross) / 2, *cross),
+                    AlignItems::Stretch => (0, line_cross),
+                };
+                let main = main_start as i64 + main_offset + space;
+                let cross_at = cross_start as i64 + line_offset + cross_offset;
+                zones.push(match self.axis {
+                    Axis::Horizontal => zone32(main, cross_at, *len, cross),
+                    Axis::Vertical => zone32(cross_at, main, cross, *len),
+                });
+                main_offset += len + gap;
+            }
+
+            line_offset += line_cross + gap;
+            first = last;
+        }
+        zones
+    }
+}
+
+/// Removes the `deficit` length from the `lengths` by `weights`,
+/// never shrinking any of them below `1`.
+fn shrink(lengths: &mut [i64], weights: &[i64], mut deficit: i64) {
+    let mut weights = weights.to_vec();
+    while deficit > 0 {
+        let total: i64 = weights.iter().sum();
+        if total == 0 {
+            return;
+        }
+        let mut shares: Vec<i64> = weights
+            .iter()
+            .map(|w| (deficit as i128 * *w as i128 / total as i128) as i64)
+            .collect();
+        let mut rest = deficit - shares.iter().sum::<i64>();
+        for (share, _) in shares.iter_mut().zip(&weights).filter(|(_, w)| **w > 0) {
+            if rest == 0 {
+                break;
+            }
+            *share += 1;
+            rest -= 1;
+        }
+
+        // freeze the items that would shrink too much, and try again
+        let mut frozen = false;
+        for ((len, w), share) in lengths.iter_mut().zip(weights.iter_mut()).zip(&shares) {
+            if *w > 0 && *len - share < 1 {
+                deficit -= *len - 1;
+                *len = 1;
+                *w = 0;
+                frozen = true;
+            }
+        }
+        if !frozen {
+            for (len, share) in lengths.iter_mut().zip(shares) {
+                *len -= share;
+            }
+            deficit = 0;
+        }
+    }
+}
@@ -0,0 +1,288 @@
+// cuadra::layout::grid
+//
+//! Grid track layout.
+//
+
+use super::{distribute, zone32};
+use crate::{Clamper32, Size32, Zone32};
+use alloc::{string::String, vec::Vec};
+
+/// The sizing of a row or column track in a [`Grid`].
+///
+/// The tracks of each axis are sized in four steps:
+///
+/// 1. `Fixed` tracks get their length, `MinMax` tracks their minimum, `Auto`
+///    tracks the longest content of their single-span items, and `Fr` tracks
+///    nothing.
+/// 2. The free length grows the `MinMax` tracks evenly, up to their maximum.
+/// 3. What's left is distributed between the `Fr` tracks, by weight.
+/// 4. If there are no `Fr` tracks, the rest is distributed evenly between the
+///    `Auto` tracks.
+///
+/// The remainders of the integer divisions go one by one to the first tracks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Track {
+    /// A fixed length.
+    Fixed(u32),
+    /// A weighted share of the free length.
+    Fr(u16),
+    /// Sized to its content, stretched if there are no `Fr` tracks.
+    Auto,
+    /// Between a minimum and a maximum length.
+    MinMax(u32, u32),
+}
+
+/// A rectangular set of cells in a [`Grid`], in track indices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GridArea {
+    /// The first column.
+    pub column: u16,
+    /// The first row.
+    pub row: u16,
+    /// The number of columns.
+    pub column_span: u16,
+    /// The number of rows.
+    pub row_span: u16,
+}
+
+impl GridArea {
+    /// Returns a new area covering the single cell at the given `column` and `row`.
+    pub const fn new(column: u16, row: u16) -> Self {
+        Self {
+            column,
+            row,
+            column_span: 1,
+            row_span: 1,
+        }
+    }
+
+    /// Chain-sets the number of columns and rows spanned, which has to be at least `1`.
+    pub const fn span(mut self, columns: u16, rows: u16) -> Self {
+        self.column_span = if columns == 0 { 1 } else { columns };
+        self.row_span = if rows == 0 { 1 } else { rows };
+        self
+    }
+}
+
+/// An item placed in a [`Grid`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GridItem {
+    /// The area of the grid the item covers.
+    pub area: GridArea,
+    /// The size of the content, used to size the `Auto` tracks.
+    pub content: Option<Size32>,
+}
+
+impl GridItem {
+    /// Returns a new item covering the given `area`, without content.
+    pub const fn new(area: GridArea) -> Self {
+        Self {
+            area,
+            content: None,
+        }
+    }
+
+    /// Chain-sets the size of the content.
+    pub const fn content(mut self, content: Size32) -> Self {
+        self.content = Some(content);
+        self
+    }
+}
+
+/// A grid layout, placing [`GridItem`]s into row and column [`Track`]s
+/// inside a [`Zone32`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Grid {
+    columns: Vec<Track>,
+    rows: Vec<Track>,
+    column_gap: i32,
+    row_gap: i32,
+    areas: Vec<(String, GridArea)>,
+}
+
+impl Grid {
+    /// Returns a new grid with the given `columns` and `rows` tracks, without gaps.
+    pub fn new(columns: &[Track], rows: &[Track]) -> Self {
+        Self {
+            columns: columns.to_vec(),
+            rows: rows.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    /// Chain-sets the gaps between columns and between rows.
+    pub fn gap(mut self, column_gap: i32, row_gap: i32) -> Self {
+        self.column_gap = Clamper32::clamp_non_negative(column_gap);
+        self.row_gap = Clamper32::clamp_non_negative(row_gap);
+        self
+    }
+
+    /// Chain-sets a named area, replacing any previous area with the same name.
+    pub fn area(mut self, name: &str, area: GridArea) -> Self {
+        match self.areas.iter_mut().find(|(n, _)| n == name) {
+            Some((_, a)) => *a = area,
+            None => self.areas.push((name.into(), area)),
+        }
+        self
+    }
+
+    /// Chain-sets the named areas from a template with a string per row.
+    ///
+    /// Each row lists a name per column, separated by whitespace, and `.` for
+    /// unnamed cells. Each area covers the bounding box of the cells with its
+    /// name, e.g.:
+    /// ```
+    /// # use cuadra::{Grid, GridArea, Track};
+    /// let grid = Grid::new(&[Track::Fixed(10), Track::Fr(1)], &[Track::Auto; 3])
+    ///     .template_areas(&["head head", "side main", ". main"]);
+    /// assert_eq![Some(GridArea::new(1, 1).span(1, 2)), grid.named_area("main")];
+    /// ```
+    pub fn template_areas(mut self, rows: &[&str]) -> Self {
+        let mut bounds: Vec<(&str, [u16; 4])> = Vec::new();
+        for (row, names) in rows.iter().enumerate() {
+            for (column, name) in names.split_whitespace().enumerate() {
+                if name == "." {
+                    continue;
+                }
+                let (column, row) = (column as u16, row as u16);
+                match bounds.iter_mut().find(|(n, _)| *n == name) {
+                    Some((_, b)) => {
+                        b[0] = b[0].min(column);
+                        b[1] = b[1].min(row);
+                        b[2] = b[2].max(column);
+                        b[3] = b[3].max(row);
+                    }
+                    None => bounds.push((name, [column, row, column, row])),
+                }
+            }
+        }
+        for (name, [c0, r0, c1, r1]) in bounds {
+            self = self.area(name, GridArea::new(c0, r0).span(c1 - c0 + 1, r1 - r0 + 1));
+        }
+        self
+    }
+
+    /// Returns the area with the given `name`, if any.
+    pub fn named_area(&self, name: &str) -> Option<GridArea> {
+        self.areas.iter().find(|(n, _)| n == name).map(|(_, a)| *a)
+    }
+
+    /// Returns the zone of each item, placed inside the `container`.
+    ///
+    /// An item gets `None` if its area falls outside the grid,
+    /// or if the tracks it covers have no length.
+    pub fn layout(&self, container: Zone32, items: &[GridItem]) -> Vec<Option<Zone32>> {
+        let columns = size_tracks(
+            &self.columns,
+            container.w() as i64,
+            self.column_gap as i64,
+            items
+                .iter()
+                .filter(|i| i.area.column_span == 1)
+                .filter_map(|i| i.content.map(|c| (i.area.column as usize, c.w() as i64))),
+        );
+        let rows = size_tracks(
+            &self.rows,
+            container.h() as i64,
+            self.row_gap as i64,
+            items
+                .iter()
+                .filter(|i| i.area.row_span == 1)
+                .filter_map(|i| i.content.map(|c| (i.area.row as usize, c.h() as i64))),
+        );
+        let column_starts = starts(container.x() as i64, &columns, self.column_gap as i64);
+        let row_starts = starts(container.y() as i64, &rows, self.row_gap as i64);
+
+        items
+            .iter()
+            .map(|item| {
+                let a = item.area;
+                let (c0, r0) = (a.column as usize, a.row as usize);
+                let (c1, r1) = (
+                    c0 + a.column_span as usize - 1,
+                    r0 + a.row_span as usize - 1,
+                );
+                if c1 >= columns.len() || r1 >= rows.len() {
+                    return None;
+                }
+                let (x, y) = (column_starts[c0], row_starts[r0]);
+                let w = column_starts[c1] + columns[c1] - x;
+                let h = row_starts[r1] + rows[r1] - y;
+                (w > 0 && h > 0).then(|| zone32(x, y, w, h))
+            })
+            .collect()
+    }
+}
+
+/// Returns the length of each track, out of the `total` length,
+/// given the `(track, length)` of each single-span content.
+fn size_tracks(
+    tracks: &[Track],
+    total: i64,
+    gap: i64,
+    contents: impl Iterator<Item = (usize, i64)>,
+) -> Vec<i64> {
+    let mut lengths: Vec<i64> = tracks
+        .iter()
+        .map(|t| match *t {
+            Track::Fixed(n) | Track::MinMax(n, _) => n as i64,
+            Track::Fr(_) | Track::Auto => 0,
+        })
+        .collect();
+    for (track, length) in contents {
+        if let Some(Track::Auto) = tracks.get(track) {
+            lengths[track] = lengths[track].max(length);
+        }
+    }
+
+    let gaps = gap * (tracks.len() as i64 - 1).max(0);
+    let free = total - gaps - lengths.iter().sum::<i64>();
+    if free <= 0 {
+        return lengths;
+    }
+
+    let weights: Vec<i64> = tracks
+        .iter()
+        .map(|t| matches!(t, Track::MinMax(..)) as i64)
+        .collect();
+    let free = distribute(
+        &mut lengths,
+        &weights,
+        |i| match tracks[i] {
+            Track::MinMax(_, max) => Some(max as i64),
+            _ => None,
+        },
+        free,
+    );
+
+    let fr: Vec<i64> = tracks
+        .iter()
+        .map(|t| match *t {
+            Track::Fr(w) => w as i64,
+            _ => 0,
+        })
+        .collect();
+    if tracks.iter().any(|t| matches!(t, Track::Fr(_))) {
+        distribute(&mut lengths, &fr, |_| None, free);
+    } else {
+        let auto: Vec<i64> = tracks
+            .iter()
+            .map(|t| matches!(t, Track::Auto) as i64)
+            .collect();
+        distribute(&mut lengths, &auto, |_| None, free);
+    }
+    lengths
+}
+
+/// Returns the start of each track, from the given `origin`.
+fn starts(origin: i64, lengths: &[i64], gap: i64) -> Vec<i64> {
+    let mut start = origin;
+    lengths
+        .iter()
+        .map(|len| {
+            let s = start;
+            start += len + gap;
+            s
+        })
+        .collect()
+}
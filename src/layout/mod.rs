@@ -0,0 +1,107 @@
+// cuadra::layout
+//
+//! Layout engines.
+//
+
+#[cfg(feature = "alloc")]
+use {
+    crate::{Clamper32, Zone32},
+    alloc::vec::Vec,
+};
+
+#[cfg(feature = "alloc")]
+mod flex;
+#[cfg(feature = "alloc")]
+mod grid;
+#[cfg(feature = "alloc")]
+mod split;
+
+#[cfg(feature = "alloc")]
+pub use flex::{AlignItems, Flex, FlexItem, JustifyContent};
+#[cfg(feature = "alloc")]
+pub use grid::{Grid, GridArea, GridItem, Track};
+#[cfg(feature = "alloc")]
+pub use split::Constraint;
+
+/// The axis along which a layout distributes its children.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Axis {
+    /// Children are placed from left to right, along `x`.
+    Horizontal,
+    /// Children are placed from top to bottom, along `y`.
+    Vertical,
+}
+
+/// Distributes the `free` length between the `lengths` by `weights`, never
+/// growing a length beyond its `cap`, and returns the length left unclaimed.
+///
+/// The remainder of the integer division goes one by one to the first lengths
+/// that can still grow.
+#[cfg(feature = "alloc")]
+fn distribute(
+    lengths: &mut [i64],
+    weights: &[i64],
+    cap: impl Fn(usize) -> Option<i64>,
+    mut free: i64,
+) -> i64 {
+    let mut growing: Vec<bool> = weights.iter().map(|w| *w > 0).collect();
+    while free > 0 {
+        let total: i64 = weights
+            .iter()
+            .zip(&growing)
+            .filter(|(_, g)| **g)
+            .map(|(w, _)| w)
+            .sum();
+        if total == 0 {
+            break;
+        }
+
+        let mut shares: Vec<i64> = weights
+            .iter()
+            .zip(&growing)
+            .map(|(w, g)| {
+                if *g {
+                    (free as i128 * *w as i128 / total as i128) as i64
+                } else {
+                    0
+                }
+            })
+            .collect();
+        let mut rest = free - shares.iter().sum::<i64>();
+        for (share, _) in shares.iter_mut().zip(&growing).filter(|(_, g)| **g) {
+            if rest == 0 {
+                break;
+            }
+            *share += 1;
+            rest -= 1;
+        }
+
+        // cap the lengths that would outgrow their maximum, and try again
+        let mut capped = false;
+        for (i, share) in shares.iter().enumerate() {
+            if let Some(cap) = cap(i) {
+                if growing[i] && lengths[i] + share >= cap {
+                    let grow = (cap - lengths[i]).max(0);
+                    lengths[i] += grow;
+                    free -= grow;
+                    growing[i] = false;
+                    capped = true;
+                }
+            }
+        }
+        if !capped {
+            for (length, share) in lengths.iter_mut().zip(shares) {
+                *length += share;
+            }
+            free = 0;
+        }
+    }
+    free
+}
+
+/// Returns a zone from wider components, clamped to the [`Clamper32`] range.
+#[cfg(feature = "alloc")]
+fn zone32(x: i64, y: i64, w: i64, h: i64) -> Zone32 {
+    let clamp = |v: i64| v.clamp(Clamper32::MIN as i64, Clamper32::MAX as i64) as i32;
+    Zone32::new_raw(clamp(x), clamp(y), clamp(w), clamp(h))
+}
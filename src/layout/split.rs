@@ -0,0 +1,123 @@
+// cuadra::layout::split
+//
+//! Constraint-based split layout.
+//
+
+use super::{distribute, Axis};
+use crate::{Zone16, Zone32};
+use alloc::vec::Vec;
+
+/// The length constraint of a child in a [`split`][Zone32::split] layout.
+///
+/// The lengths are solved in three steps:
+///
+/// 1. Each child gets its base length: `Length` and `Min` their value,
+///    `Percentage` and `Ratio` their rounded down share of the total length,
+///    and `Max` and `Fill` nothing.
+/// 2. If the base lengths overflow the total length, they are truncated in
+///    order, so the last children are the first ones to shrink.
+/// 3. The leftover length is distributed between `Fill` children, by weight,
+///    and `Min` and `Max` children, with a weight of `1`, never growing a `Max`
+///    child beyond its value. The remainder of the integer division goes one
+///    by one to the first of those children. Any leftover length not taken by
+///    any of them is left empty at the end.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Constraint {
+    /// A fixed length.
+    Length(u32),
+    /// A percentage of the total length.
+    Percentage(u16),
+    /// A ratio of the total length, as a numerator and a denominator.
+    Ratio(u32, u32),
+    /// A minimum length, growing with the leftover length.
+    Min(u32),
+    /// A maximum length, growing with the leftover length up to its value.
+    Max(u32),
+    /// A weighted share of the leftover length.
+    Fill(u16),
+}
+
+impl Constraint {
+    /// Returns the length this constraint takes before distributing the leftover.
+    const fn base(&self, total: i64) -> i64 {
+        match *self {
+            Constraint::Length(n) | Constraint::Min(n) => n as i64,
+            Constraint::Percentage(p) => total * p as i64 / 100,
+            Constraint::Ratio(_, 0) => 0,
+            Constraint::Ratio(n, d) => total * n as i64 / d as i64,
+            Constraint::Max(_) | Constraint::Fill(_) => 0,
+        }
+    }
+
+    /// Returns the weight with which this constraint takes leftover length.
+    const fn weight(&self) -> i64 {
+        match *self {
+            Constraint::Fill(w) => w as i64,
+            Constraint::Min(_) | Constraint::Max(_) => 1,
+            _ => 0,
+        }
+    }
+
+    /// Returns the maximum length this constraint can grow to, if any.
+    const fn cap(&self) -> Option<i64> {
+        match *self {
+            Constraint::Max(n) => Some(n as i64),
+            _ => None,
+        }
+    }
+}
+
+/// Solves the length of each constraint, out of the `total` length.
+fn solve(total: i64, constraints: &[Constraint]) -> Vec<i64> {
+    let mut remaining = total;
+    let mut lengths: Vec<i64> = constraints
+        .iter()
+        .map(|c| {
+            let length = c.base(total).min(remaining);
+            remaining -= length;
+            length
+        })
+        .collect();
+
+    let weights: Vec<i64> = constraints.iter().map(|c| c.weight()).collect();
+    distribute(&mut lengths, &weights, |i| constraints[i].cap(), remaining);
+    lengths
+}
+
+macro_rules! split {
+    ( $($i:ty, $b:expr),+ ) => {
+        $( split![single: $i, $b]; )+
+    };
+
+    (single: $i:ty, $b:literal) => { paste::paste! {
+        /// # layout
+        impl [<Zone$b>] {
+            /// Splits the zone along the `axis` into a child zone per constraint.
+            ///
+            /// Children are placed contiguously from the start of the zone, and
+            /// keep the full length of the zone along the other axis.
+            /// A child that gets no length at all is returned as `None`.
+            ///
+            /// See [`Constraint`] for how the lengths are solved.
+            pub fn split(&self, axis: Axis, constraints: &[Constraint]) -> Vec<Option<[<Zone$b>]>> {
+                let (start, total) = match axis {
+                    Axis::Horizontal => (self.x(), self.w()),
+                    Axis::Vertical => (self.y(), self.h()),
+                };
+                let mut offset = start as i64;
+                solve(total as i64, constraints)
+                    .into_iter()
+                    .map(|length| {
+                        let (at, len) = (offset as $i, length as $i);
+                        offset += length;
+                        (length > 0).then(|| match axis {
+                            Axis::Horizontal => Self::new_raw(at, self.y(), len, self.h()),
+                            Axis::Vertical => Self::new_raw(self.x(), at, self.w(), len),
+                        })
+                    })
+                    .collect()
+            }
+        }
+    }};
+}
+split![i16, 16, i32, 32];
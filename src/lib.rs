@@ -9,6 +9,9 @@
 //! to values of just half the range of an equivalent bit-size signed integer,
 //! leaving 1/4th of the range for positive values.
 //!
+//! For example [`Position8`] is an alias of [`Position`]`<i8>`, and code generic
+//! over the bit size can be written for any inner [`Coord`].
+//!
 //! Positions can have negative values, while Sizes can only be positive.
 //!
 //! For example, a [`Position8`] can hold values between `-64,-64` and `63,63`,
@@ -46,15 +49,79 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![forbid(unsafe_code)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 #[cfg(test)]
 mod tests;
 
+mod align;
+#[cfg(feature = "alloc")]
+mod atlas;
 mod clamper;
+#[cfg(feature = "serde")]
+pub mod compact;
+mod convert;
+mod coord;
+#[cfg(feature = "alloc")]
+mod damage;
+mod direction;
+mod insets;
+mod layout;
+mod offset;
+#[cfg(feature = "alloc")]
+mod pack;
+mod parse;
 mod position;
+mod positions;
+mod raster;
+#[cfg(feature = "alloc")]
+mod region;
+#[cfg(feature = "serde")]
+mod serialize;
 mod size;
+#[cfg(feature = "alloc")]
+mod spatial;
 mod zone;
 
+pub use align::{Align, Anchor};
+#[cfg(feature = "alloc")]
+pub use atlas::{AllocId, AtlasAllocator, AtlasStats};
 pub use clamper::{Clamper16, Clamper32, Clamper64, Clamper8};
-pub use position::{Position16, Position32, Position64, Position8};
-pub use size::{Size16, Size32, Size64, Size8};
-pub use zone::{Zone16, Zone32, Zone64, Zone8};
+pub use convert::{ClampFrom, ClampInto, OutOfRangeError};
+pub use coord::Coord;
+#[cfg(feature = "alloc")]
+pub use damage::DamageTracker;
+pub use direction::{Direction4, Direction8, Neighbors};
+pub use insets::{Insets16, Insets32, Insets64, Insets8};
+pub use layout::Axis;
+#[cfg(feature = "alloc")]
+pub use layout::{
+    AlignItems, Constraint, Flex, FlexItem, Grid, GridArea, GridItem, JustifyContent, Track,
+};
+pub use offset::{Offset, Offset16, Offset32, Offset64, Offset8};
+#[cfg(feature = "alloc")]
+pub use pack::{PackAlgorithm, Packer, Packing};
+pub use parse::{Component, ParseError, ParseErrorKind};
+pub use position::{Position, Position16, Position32, Position64, Position8};
+pub use positions::{
+    PositionOrder, ZonePositions16, ZonePositions32, ZonePositions64, ZonePositions8,
+};
+pub use raster::{
+    Bezier32, Ellipse16, Ellipse32, Ellipse8, EllipseOutline16, EllipseOutline32, EllipseOutline8,
+    EllipseSpans16, EllipseSpans32, EllipseSpans8, Line16, Line32, Line64, Line8,
+};
+#[cfg(feature = "alloc")]
+pub use raster::{FillRule, PolygonSpans32};
+#[cfg(feature = "alloc")]
+pub use region::Region32;
+pub use size::{Size, Size16, Size32, Size64, Size8};
+#[cfg(feature = "alloc")]
+pub use spatial::{
+    Metric, QuadTree32, QuadTreeId, RTree32, RTree64, RTreeQuery32, RTreeQuery64, SpatialHash,
+    SpatialHashId,
+};
+pub use zone::{
+    Zone, Zone16, Zone32, Zone64, Zone8, ZoneDifference, ZoneDifference16, ZoneDifference32,
+    ZoneDifference64, ZoneDifference8,
+};
@@ -0,0 +1,189 @@
+// cuadra::offset
+//
+//! 2D offsets.
+//
+
+use crate::{Coord, Position, Size};
+use core::{
+    fmt,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+/// A 2D offset between positions, using signed [`Coord`]s.
+///
+/// Unlike a [`Position`], its `dx, dy` components are not clamped, so that
+/// they can represent the offset between any two positions. The arithmetic
+/// operations saturate at the limits of the inner primitive.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Offset<T> {
+    dx: T,
+    dy: T,
+}
+
+/// A 2D offset using an [`i8`].
+pub type Offset8 = Offset<i8>;
+/// A 2D offset using an [`i16`].
+pub type Offset16 = Offset<i16>;
+/// A 2D offset using an [`i32`].
+pub type Offset32 = Offset<i32>;
+/// A 2D offset using an [`i64`].
+pub type Offset64 = Offset<i64>;
+
+impl<T: Coord> fmt::Debug for Offset<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Offset{} {{ dx: {}, dy: {} }}",
+            T::BITS,
+            self.dx,
+            self.dy,
+        )
+    }
+}
+
+impl<T: Coord> fmt::Display for Offset<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "dx:{} dy:{}", self.dx, self.dy)
+    }
+}
+
+impl<T: Coord> Offset<T> {
+    /// Defines a new `Offset` with the given components.
+    pub const fn new(dx: T, dy: T) -> Self {
+        Self { dx, dy }
+    }
+
+    /// Get the horizontal offset.
+    #[inline]
+    pub const fn dx(&self) -> T {
+        self.dx
+    }
+    /// Get the vertical offset.
+    #[inline]
+    pub const fn dy(&self) -> T {
+        self.dy
+    }
+
+    /// Returns a tuple with the `(dx, dy)` components.
+    pub const fn as_tuple(&self) -> (T, T) {
+        (self.dx, self.dy)
+    }
+}
+
+/// # offsets
+impl<T: Coord> Position<T> {
+    /// Returns the offset from this position to the `other`.
+    pub fn offset_to(&self, other: Position<T>) -> Offset<T> {
+        other - *self
+    }
+}
+
+/// # offsets
+impl<T: Coord> Size<T> {
+    /// Returns a new `Size` with the absolute extents of the `offset`,
+    /// which has to be at least `1`.
+    pub fn from_offset(offset: Offset<T>) -> Self {
+        let abs = |v: T| {
+            if v < T::ZERO {
+                T::ZERO.saturating_sub(v)
+            } else {
+                v
+            }
+        };
+        Size::from_wh(abs(offset.dx), abs(offset.dy))
+    }
+}
+
+/* impl ops */
+
+impl<T: Coord> Add<Offset<T>> for Position<T> {
+    type Output = Position<T>;
+
+    /// Saturating, clamped translation.
+    #[inline]
+    fn add(self, rhs: Offset<T>) -> Position<T> {
+        Position::from_xy(
+            self.x().saturating_add(rhs.dx),
+            self.y().saturating_add(rhs.dy),
+        )
+    }
+}
+impl<T: Coord> Sub<Offset<T>> for Position<T> {
+    type Output = Position<T>;
+
+    /// Saturating, clamped translation.
+    #[inline]
+    fn sub(self, rhs: Offset<T>) -> Position<T> {
+        Position::from_xy(
+            self.x().saturating_sub(rhs.dx),
+            self.y().saturating_sub(rhs.dy),
+        )
+    }
+}
+impl<T: Coord> Sub for Position<T> {
+    type Output = Offset<T>;
+
+    /// Returns the offset from `rhs` to `self`.
+    #[inline]
+    fn sub(self, rhs: Position<T>) -> Offset<T> {
+        Offset::new(
+            self.x().saturating_sub(rhs.x()),
+            self.y().saturating_sub(rhs.y()),
+        )
+    }
+}
+
+impl<T: Coord> Add for Offset<T> {
+    type Output = Self;
+
+    /// Saturating addition.
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self::new(
+            self.dx.saturating_add(rhs.dx),
+            self.dy.saturating_add(rhs.dy),
+        )
+    }
+}
+impl<T: Coord> Sub for Offset<T> {
+    type Output = Self;
+
+    /// Saturating substraction.
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(
+            self.dx.saturating_sub(rhs.dx),
+            self.dy.saturating_sub(rhs.dy),
+        )
+    }
+}
+impl<T: Coord> Neg for Offset<T> {
+    type Output = Self;
+
+    /// Saturating negation.
+    #[inline]
+    fn neg(self) -> Self {
+        Self::new(
+            T::ZERO.saturating_sub(self.dx),
+            T::ZERO.saturating_sub(self.dy),
+        )
+    }
+}
+impl<T: Coord> Mul<T> for Offset<T> {
+    type Output = Self;
+
+    /// Saturating multiplication.
+    #[inline]
+    fn mul(self, rhs: T) -> Self {
+        Self::new(self.dx.saturating_mul(rhs), self.dy.saturating_mul(rhs))
+    }
+}
+impl<T: Coord> Div<T> for Offset<T> {
+    type Output = Self;
+
+    /// Saturating division.
+    #[inline]
+    fn div(self, rhs: T) -> Self {
+        Self::new(self.dx.saturating_div(rhs), self.dy.saturating_div(rhs))
+    }
+}
@@ -0,0 +1,341 @@
+// cuadra::pack
+//
+//! Rectangle bin packing.
+//
+
+use crate::{Size16, Zone16};
+use alloc::vec::Vec;
+
+/// The algorithm used by a [`Packer`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PackAlgorithm {
+    /// Items are placed in rows as tall as their first item.
+    ///
+    /// It's the fastest, and wastes the most space with uneven heights.
+    Shelf,
+    /// Items are placed at the lowest and leftmost spot of the skyline
+    /// formed by the items placed so far.
+    #[default]
+    Skyline,
+    /// Items are placed in the free rectangle that fits them best, which is
+    /// then split in two along the shorter leftover axis.
+    Guillotine,
+    /// Items are placed in the maximal free rectangle with the shortest
+    /// leftover side.
+    ///
+    /// It's the slowest, and usually packs the tightest.
+    MaxRects,
+}
+
+/// Packs a list of [`Size16`] items into a bin.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Packer {
+    algorithm: PackAlgorithm,
+    rotation: bool,
+}
+
+impl Packer {
+    /// Returns a new packer using the given `algorithm`, without rotation.
+    pub const fn new(algorithm: PackAlgorithm) -> Self {
+        Self {
+            algorithm,
+            rotation: false,
+        }
+    }
+
+    /// Chain-sets whether items can be rotated by 90 degrees to fit better.
+    ///
+    /// A rotated item gets a zone with its [`swapped`][Size16::swapped] size.
+    pub const fn rotation(mut self, rotation: bool) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Packs the `items` into a `bin` with its origin at `0, 0`.
+    ///
+    /// Items are packed from the longest to the shortest, for better results,
+    /// and their placements are returned in their original order.
+    pub fn pack(&self, bin: Size16, items: &[Size16]) -> Packing {
+        let mut order: Vec<usize> = (0..items.len()).collect();
+        order.sort_by_key(|&i| {
+            let (w, h) = items[i].as_tuple();
+            (core::cmp::Reverse(w.max(h)), core::cmp::Reverse(w.min(h)))
+        });
+
+        let bin = Rect::new(0, 0, bin.w() as i32, bin.h() as i32);
+        let mut state = match self.algorithm {
+            PackAlgorithm::Shelf => State::Shelf(Vec::new()),
+            PackAlgorithm::Skyline => State::Skyline(Vec::from([(0, 0, bin.w)])),
+            PackAlgorithm::Guillotine => State::Guillotine(Vec::from([bin])),
+            PackAlgorithm::MaxRects => State::MaxRects(Vec::from([bin])),
+        };
+
+        let mut placements = alloc::vec![None; items.len()];
+        for i in order {
+            let (w, h) = (items[i].w() as i32, items[i].h() as i32);
+            let (both, upright) = ([(w, h), (h, w)], [(w, h)]);
+            let orientations: &[(i32, i32)] = if self.rotation && w != h {
+                &both
+            } else {
+                &upright
+            };
+            placements[i] = state
+                .place(bin, orientations)
+                .map(|r| Zone16::new_raw(r.x as i16, r.y as i16, r.w as i16, r.h as i16));
+        }
+        Packing { placements }
+    }
+}
+
+/// The result of [`Packer::pack`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Packing {
+    placements: Vec<Option<Zone16>>,
+}
+
+impl Packing {
+    /// Returns the placement of each item, or `None` if it didn't fit.
+    pub fn placements(&self) -> &[Option<Zone16>] {
+        &self.placements
+    }
+
+    /// Returns the indices of the items that didn't fit.
+    pub fn unplaced(&self) -> impl Iterator<Item = usize> + '_ {
+        self.placements
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.is_none())
+            .map(|(i, _)| i)
+    }
+
+    /// Returns `true` if all the items fit.
+    pub fn is_complete(&self) -> bool {
+        self.placements.iter().all(Option::is_some)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Rect {
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+}
+
+impl Rect {
+    const fn new(x: i32, y: i32, w: i32, h: i32) -> Self {
+        Self { x, y, w, h }
+    }
+    const fn x_end(&self) -> i32 {
+        self.x + self.w
+    }
+    const fn y_end(&self) -> i32 {
+        self.y + self.h
+    }
+    const fn area(&self) -> i64 {
+        self.w as i64 * self.h as i64
+    }
+    const fn overlaps(&self, other: &Rect) -> bool {
+        self.x < other.x_end()
+            && other.x < self.x_end()
+            && self.y < other.y_end()
+            && other.y < self.y_end()
+    }
+    const fn contains(&self, other: &Rect) -> bool {
+        other.x >= self.x
+            && other.x_end() <= self.x_end()
+            && other.y >= self.y
+            && other.y_end() <= self.y_end()
+    }
+}
+
+/// The free space tracked by each algorithm.
+enum State {
+    /// The `(y, height, used width)` of each shelf.
+    Shelf(Vec<(i32, i32, i32)>),
+    /// The `(x, y, width)` of each skyline segment, from left to right.
+    Skyline(Vec<(i32, i32, i32)>),
+    /// The disjoint free rectangles.
+    Guillotine(Vec<Rect>),
+    /// The maximal, possibly overlapping, free rectangles.
+    MaxRects(Vec<Rect>),
+}
+
+impl State {
+    /// Places an item in the best of its `orientations`, if it fits.
+    fn place(&mut self, bin: Rect, orientations: &[(i32, i32)]) -> Option<Rect> {
+        match self {
+            State::Shelf(shelves) => {
+                // the existing shelf that wastes the least height
+                let mut best: Option<(i32, usize, i32, i32)> = None;
+                for (s, &(_, height, used)) in shelves.iter().enumerate() {
+                    for &(w, h) in orientations {
+                        if used + w <= bin.w && h <= height {
+                            let waste = height - h;
+                            if best.map_or(true, |b| waste < b.0) {
+                                best = Some((waste, s, w, h));
+                            }
+                        }
+                    }
+                }
+                if let Some((_, s, w, h)) = best {
+                    let (y, _, used) = &mut shelves[s];
+                    let rect = Rect::new(*used, *y, w, h);
+                    *used += w;
+                    return Some(rect);
+                }
+
+                // otherwise a new shelf, as short as possible
+                let top = shelves.last().map_or(0, |&(y, h, _)| y + h);
+                let &(w, h) = orientations
+                    .iter()
+                    .filter(|&&(w, h)| w <= bin.w && top + h <= bin.h)
+                    .min_by_key(|&&(_, h)| h)?;
+                shelves.push((top, h, w));
+                Some(Rect::new(0, top, w, h))
+            }
+
+            State::Skyline(skyline) => {
+                // the lowest, then leftmost, spot
+                let mut best: Option<(i32, i32, usize, Rect)> = None;
+                for &(w, h) in orientations {
+                    for s in 0..skyline.len() {
+                        let x = skyline[s].0;
+                        if x + w > bin.w {
+                            break;
+                        }
+                        let y = skyline[s..]
+                            .iter()
+                            .take_while(|seg| seg.0 < x + w)
+                            .map(|seg| seg.1)
+                            .max()
+                            .unwrap_or(0);
+                        if y + h <= bin.h && best.map_or(true, |b| (y + h, x) < (b.0, b.1)) {
+                            best = Some((y + h, x, s, Rect::new(x, y, w, h)));
+                        }
+                    }
+                }
+                let (_, _, s, rect) = best?;
+
+                // raise the skyline under the new item
+                let mut segments = Vec::with_capacity(skyline.len() + 2);
+                segments.extend_from_slice(&skyline[..s]);
+                segments.push((rect.x, rect.y_end(), rect.w));
+                for &(x, y, w) in &skyline[s..] {
+                    if x + w > rect.x_end() {
+                        let start = x.max(rect.x_end());
+                        segments.push((start, y, x + w - start));
+                    }
+                }
+                segments.dedup_by(|next, prev| {
+                    let merge = prev.1 == next.1;
+                    if merge {
+                        prev.2 += next.2;
+                    }
+                    merge
+                });
+                *skyline = segments;
+                Some(rect)
+            }
+
+            State::Guillotine(free) => {
+                // the free rectangle that fits best by area
+                let mut best: Option<(i64, usize, i32, i32)> = None;
+                for (f, r) in free.iter().enumerate() {
+                    for &(w, h) in orientations {
+                        if w <= r.w && h <= r.h {
+                            let waste = r.area() - w as i64 * h as i64;
+                            if best.map_or(true, |b| waste < b.0) {
+                                best = Some((waste, f, w, h));
+                            }
+                        }
+                    }
+                }
+                let (_, f, w, h) = best?;
+                let r = free.swap_remove(f);
+
+                // split the leftover along the shorter axis
+                let (right, bottom) = if r.w - w < r.h - h {
+                    (
+                        Rect::new(r.x + w, r.y, r.w - w, h),
+                        Rect::new(r.x, r.y + h, r.w, r.h - h),
+                    )
+                } else {
+                    (
+                        Rect::new(r.x + w, r.y, r.w - w, r.h),
+                        Rect::new(r.x, r.y + h, w, r.h - h),
+                    )
+                };
+                free.extend([right, bottom].into_iter().filter(|r| r.w > 0 && r.h > 0));
+                Some(Rect::new(r.x, r.y, w, h))
+            }
+
+            State::MaxRects(free) => {
+                // the free rectangle with the shortest, then longest, leftover side
+                let mut best: Option<((i32, i32), Rect)> = None;
+                for r in free.iter() {
+                    for &(w, h) in orientations {
+                        if w <= r.w && h <= r.h {
+                            let (dw, dh) = (r.w - w, r.h - h);
+                            let fit = (dw.min(dh), dw.max(dh));
+                            if best.map_or(true, |b| fit < b.0) {
+                                best = Some((fit, Rect::new(r.x, r.y, w, h)));
+                            }
+                        }
+                    }
+                }
+                let (_, rect) = best?;
+
+                // split every free rectangle overlapping the item into maximal ones
+                let mut split = Vec::with_capacity(8);
+                free.retain(|r| {
+                    if !r.overlaps(&rect) {
+                        return true;
+                    }
+                    if rect.x > r.x {
+                        split.push(Rect::new(r.x, r.y, rect.x - r.x, r.h));
+                    }
+                    if rect.x_end() < r.x_end() {
+                        split.push(Rect::new(rect.x_end(), r.y, r.x_end() - rect.x_end(), r.h));
+                    }
+                    if rect.y > r.y {
+                        split.push(Rect::new(r.x, r.y, r.w, rect.y - r.y));
+                    }
+                    if rect.y_end() < r.y_end() {
+                        split.push(Rect::new(r.x, rect.y_end(), r.w, r.y_end() - rect.y_end()));
+                    }
+                    false
+                });
+                let start = free.len();
+                free.extend(split);
+                prune_contained(free, start, Rect::contains);
+                Some(rect)
+            }
+        }
+    }
+}
+
+/// Removes the rectangles from the `start` index onwards that are contained in
+/// another one, keeping the first of any duplicates.
+///
+/// The ones before `start` must not be contained in each other, like the
+/// untouched maximal free rectangles after splitting the overlapping ones.
+pub(crate) fn prune_contained<R: Copy + PartialEq>(
+    rects: &mut Vec<R>,
+    start: usize,
+    contains: fn(&R, &R) -> bool,
+) {
+    let mut i = start;
+    while i < rects.len() {
+        let r = rects[i];
+        let contained = rects
+            .iter()
+            .enumerate()
+            .any(|(j, o)| i != j && contains(o, &r) && (*o != r || j < i));
+        if contained {
+            rects.swap_remove(i);
+        } else {
+            i += 1;
+        }
+    }
+}
@@ -0,0 +1,288 @@
+// cuadra::parse
+//
+//! Parsing from strings.
+//
+
+use crate::{Coord, Insets16, Insets32, Insets64, Insets8, Position, Size, Zone};
+use core::{fmt, str::FromStr};
+
+/// A component of a parsed type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Component {
+    /// The `x` coordinate.
+    X,
+    /// The `y` coordinate.
+    Y,
+    /// The width.
+    W,
+    /// The height.
+    H,
+    /// The top inset.
+    Top,
+    /// The right inset.
+    Right,
+    /// The bottom inset.
+    Bottom,
+    /// The left inset.
+    Left,
+}
+
+impl Component {
+    /// Returns the label used in the `Display` format.
+    const fn label(&self) -> &'static str {
+        match self {
+            Component::X => "x",
+            Component::Y => "y",
+            Component::W => "w",
+            Component::H => "h",
+            Component::Top => "t",
+            Component::Right => "r",
+            Component::Bottom => "b",
+            Component::Left => "l",
+        }
+    }
+}
+
+impl fmt::Display for Component {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Component::Top => "top",
+            Component::Right => "right",
+            Component::Bottom => "bottom",
+            Component::Left => "left",
+            _ => self.label(),
+        };
+        f.write_str(name)
+    }
+}
+
+/// The kind of a [`ParseError`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParseErrorKind {
+    /// The string doesn't follow any of the accepted formats.
+    Format,
+    /// The component is missing.
+    Missing,
+    /// The component is not an integer.
+    Invalid,
+    /// The component is an integer out of the clamped range,
+    /// so it would have been clamped.
+    Clamped,
+}
+
+/// The error returned when parsing a [`Position`], a [`Size`], a [`Zone`]
+/// or insets from a string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ParseError {
+    component: Option<Component>,
+    kind: ParseErrorKind,
+}
+
+impl ParseError {
+    const fn new(component: Component, kind: ParseErrorKind) -> Self {
+        Self {
+            component: Some(component),
+            kind,
+        }
+    }
+    const fn format() -> Self {
+        Self {
+            component: None,
+            kind: ParseErrorKind::Format,
+        }
+    }
+
+    /// Returns the component that failed,
+    /// or `None` if the error is about the format.
+    pub const fn component(&self) -> Option<Component> {
+        self.component
+    }
+    /// Returns the kind of error.
+    pub const fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+    /// Returns `true` if the component would have been clamped.
+    pub const fn would_clamp(&self) -> bool {
+        matches!(self.kind, ParseErrorKind::Clamped)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.component, self.kind) {
+            (Some(c), ParseErrorKind::Missing) => write!(f, "missing {} component", c),
+            (Some(c), ParseErrorKind::Invalid) => write!(f, "invalid {} component", c),
+            (Some(c), ParseErrorKind::Clamped) => write!(f, "{} component out of range", c),
+            _ => write!(f, "unrecognized format"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Returns the `N` components of `s`, in the order of the `labels`.
+///
+/// They can be labeled and separated by whitespace, like `x:1 y:2`,
+/// or unlabeled and separated by commas, like `1,2`.
+fn components<const N: usize>(s: &str, labels: [Component; N]) -> Result<[&str; N], ParseError> {
+    let mut parts = [""; N];
+    if s.contains(':') {
+        let mut tokens = s.split_whitespace();
+        for (part, component) in parts.iter_mut().zip(labels) {
+            let token = tokens
+                .next()
+                .ok_or(ParseError::new(component, ParseErrorKind::Missing))?;
+            *part = match token.split_once(':') {
+                Some((label, value)) if label == component.label() => value,
+                _ => return Err(ParseError::format()),
+            };
+        }
+        if tokens.next().is_some() {
+            return Err(ParseError::format());
+        }
+    } else {
+        let mut tokens = s.split(',');
+        for (part, component) in parts.iter_mut().zip(labels) {
+            *part = tokens
+                .next()
+                .ok_or(ParseError::new(component, ParseErrorKind::Missing))?
+                .trim();
+        }
+        if tokens.next().is_some() {
+            return Err(ParseError::format());
+        }
+    }
+    Ok(parts)
+}
+
+/// Parses the `component` from `s`, and fails if `clamp` would change it.
+fn coord<T: Coord + FromStr>(
+    s: &str,
+    component: Component,
+    clamp: fn(T) -> T,
+) -> Result<T, ParseError> {
+    if s.is_empty() {
+        return Err(ParseError::new(component, ParseErrorKind::Missing));
+    }
+    match s.parse::<T>() {
+        Ok(v) if clamp(v) == v => Ok(v),
+        Ok(_) => Err(ParseError::new(component, ParseErrorKind::Clamped)),
+        // an integer out of the range of the primitive
+        Err(_) if s.parse::<i128>().is_ok() => {
+            Err(ParseError::new(component, ParseErrorKind::Clamped))
+        }
+        Err(_) => Err(ParseError::new(component, ParseErrorKind::Invalid)),
+    }
+}
+
+/// Parses an X11 geometry string, like `WxH+X+Y`, as `[x, y, w, h]`.
+///
+/// A `-` sign before a coordinate makes it negative.
+fn geometry(s: &str) -> Option<[&str; 4]> {
+    let (w, rest) = s.split_once('x')?;
+    let (h, rest) = rest.split_at(rest.find(['+', '-'])?);
+    let (x, rest) = geometry_offset(rest)?;
+    let (y, rest) = geometry_offset(rest)?;
+    rest.is_empty().then_some([x, y, w, h])
+}
+
+/// Splits a signed geometry coordinate, like `+1`, `-1` or `+-1`, from the
+/// start of `s`, and returns it without the `+` sign, followed by the rest.
+fn geometry_offset(s: &str) -> Option<(&str, &str)> {
+    let s = match s.strip_prefix('+') {
+        Some(s) => s,
+        None if s.starts_with('-') => s,
+        None => return None,
+    };
+    let sign = usize::from(s.starts_with('-'));
+    let end = s[sign..].find(['+', '-']).map_or(s.len(), |end| sign + end);
+    (end > sign).then(|| s.split_at(end))
+}
+
+/// Accepts the `Display` format, like `x:1 y:2`, and a pair of comma
+/// separated integers, like `1,2`.
+///
+/// Fails on values that would be clamped.
+impl<T: Coord + FromStr> FromStr for Position<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let [x, y] = components(s.trim(), [Component::X, Component::Y])?;
+        let x = coord(x, Component::X, T::clamp_coord)?;
+        let y = coord(y, Component::Y, T::clamp_coord)?;
+        Ok(Position::from_xy(x, y))
+    }
+}
+
+/// Accepts the `Display` format, like `w:3 h:4`, and a pair of integers
+/// separated by a comma or by an `x`, like `3,4` or `3x4`.
+///
+/// Fails on values that would be clamped, including non-positive ones.
+impl<T: Coord + FromStr> FromStr for Size<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let s = s.trim();
+        let [w, h] = match s.split_once('x') {
+            Some((w, h)) if !s.contains([':', ',']) => [w.trim(), h.trim()],
+            _ => components(s, [Component::W, Component::H])?,
+        };
+        let w = coord(w, Component::W, T::clamp_positive)?;
+        let h = coord(h, Component::H, T::clamp_positive)?;
+        Ok(Size::from_wh(w, h))
+    }
+}
+
+/// Accepts the `Display` format, like `x:1 y:2 w:3 h:4`, four comma
+/// separated integers, like `1,2,3,4`, and an X11 geometry, like `3x4+1+2`.
+///
+/// Fails on values that would be clamped.
+impl<T: Coord + FromStr> FromStr for Zone<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let s = s.trim();
+        let [x, y, w, h] = if s.contains([':', ',']) {
+            let c = [Component::X, Component::Y, Component::W, Component::H];
+            components(s, c)?
+        } else {
+            geometry(s).ok_or(ParseError::format())?
+        };
+        Ok(Zone::from_xywh(
+            coord(x, Component::X, T::clamp_coord)?,
+            coord(y, Component::Y, T::clamp_coord)?,
+            coord(w, Component::W, T::clamp_positive)?,
+            coord(h, Component::H, T::clamp_positive)?,
+        ))
+    }
+}
+
+macro_rules! parse_insets {
+    ( $($i:ty, $b:expr),+ ) => {
+        $( parse_insets![single: $i, $b]; )+
+    };
+
+    (single: $i:ty, $b:literal) => { paste::paste! {
+        /// Accepts the `Display` format, like `t:1 r:2 b:3 l:4`, and four
+        /// comma separated integers in clockwise order, like `1,2,3,4`.
+        ///
+        /// Fails on values that would be clamped, including negative ones.
+        impl FromStr for [<Insets$b>] {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<Self, ParseError> {
+                use Component::{Bottom, Left, Right, Top};
+                let [t, r, b, l] = components(s.trim(), [Top, Right, Bottom, Left])?;
+                let clamp = <$i as Coord>::clamp_non_negative;
+                Ok(Self::new(
+                    coord(t, Top, clamp)?,
+                    coord(r, Right, clamp)?,
+                    coord(b, Bottom, clamp)?,
+                    coord(l, Left, clamp)?,
+                ))
+            }
+        }
+    }};
+}
+parse_insets![i8, 8, i16, 16, i32, 32, i64, 64];
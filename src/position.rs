@@ -1,13 +1,72 @@
 // cuadra::position
 //
-//!
+//! 2D positions.
 //
 
+use crate::Coord;
 use core::{
     fmt,
     ops::{Add, Div, Mul, Sub},
 };
 
+/// A 2D position using clamped [`Coord`]s.
+///
+/// The `x, y` components are clamped to the range of their [`Coord`].
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position<T> {
+    x: T,
+    y: T,
+}
+
+impl<T: Coord> fmt::Debug for Position<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Position{} {{ x: {}, y: {} }}", T::BITS, self.x, self.y,)
+    }
+}
+
+impl<T: Coord> fmt::Display for Position<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "x:{} y:{}", self.x, self.y)
+    }
+}
+
+impl<T: Coord> Position<T> {
+    /// Defines a new `Position` with the given dimensions, for any [`Coord`].
+    pub fn from_xy(x: T, y: T) -> Self {
+        Self {
+            x: x.clamp_coord(),
+            y: y.clamp_coord(),
+        }
+    }
+
+    /// Get x.
+    #[inline]
+    pub const fn x(&self) -> T {
+        self.x
+    }
+    /// Get y.
+    #[inline]
+    pub const fn y(&self) -> T {
+        self.y
+    }
+
+    /// Set x.
+    #[inline]
+    pub fn set_x(&mut self, x: T) {
+        self.x = x.clamp_coord();
+    }
+    /// Set y.
+    #[inline]
+    pub fn set_y(&mut self, y: T) {
+        self.y = y.clamp_coord();
+    }
+
+    /// Swaps the `x, y` values.
+    pub fn swap(&mut self) {
+        core::mem::swap(&mut self.y, &mut self.x)
+    }
+}
+
 macro_rules! position {
     // $i: inner primitive
     // $b: bit size
@@ -19,23 +78,7 @@ macro_rules! position {
         use super::[<Clamper$b>] as [<C$b>];
 
         #[doc = "A 2D position using a clamped [`" $i "`]."]
-        #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
-        pub struct [<Position$b>] {
-            x: $i,
-            y: $i,
-        }
-
-        impl fmt::Debug for [<Position$b>] {
-            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                write!(f, "{} {{ x: {}, y: {} }}", stringify!([<Position$b>]), self.x, self.y,)
-            }
-        }
-
-        impl fmt::Display for [<Position$b>] {
-            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                write!(f, "x:{} y:{}", self.x, self.y)
-            }
-        }
+        pub type [<Position$b>] = Position<$i>;
 
         impl [<Position$b>] {
             /// Defines a new `Position` with the given dimensions.
@@ -46,33 +89,6 @@ macro_rules! position {
                 }
             }
 
-            /// Get x.
-            #[inline]
-            pub const fn x(&self) -> $i {
-                self.x
-            }
-            /// Get y.
-            #[inline]
-            pub const fn y(&self) -> $i {
-                self.y
-            }
-
-            /// Set x.
-            #[inline]
-            pub fn set_x(&mut self, x: $i) {
-                self.x = [<C$b>]::clamp(x);
-            }
-            /// Set y.
-            #[inline]
-            pub fn set_y(&mut self, y: $i) {
-                self.y = [<C$b>]::clamp(y);
-            }
-
-            /// Swaps the `x, y` values.
-            pub fn swap(&mut self) {
-                core::mem::swap(&mut self.y, &mut self.x)
-            }
-
             #[doc = "Returns a new `Position"$b  "` with the `x, y` values swapped."]
             pub const fn swapped(&self) -> [<Position$b>] {
                 Self::new(self.y, self.x)
@@ -396,24 +412,6 @@ macro_rules! position {
 
         /* impl ops */
 
-        impl Add for [<Position$b>] {
-            type Output = Self;
-
-            /// Saturating, clamped addition.
-            #[inline]
-            fn add(self, rhs: Self) -> Self {
-                self.saturating_add(rhs)
-            }
-        }
-        impl Sub for [<Position$b>] {
-            type Output = Self;
-
-            /// Saturating, clamped addition.
-            #[inline]
-            fn sub(self, rhs: Self) -> Self {
-                self.saturating_sub(rhs)
-            }
-        }
         impl Mul for [<Position$b>] {
             type Output = Self;
 
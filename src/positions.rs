@@ -0,0 +1,229 @@
+// cuadra::positions
+//
+//! Iteration over the positions of a zone.
+//
+
+use core::iter::FusedIterator;
+
+/// The order in which the positions of a zone are visited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PositionOrder {
+    /// Row by row from the top, each one from left to right.
+    #[default]
+    RowMajor,
+    /// Column by column from the left, each one from top to bottom.
+    ColumnMajor,
+    /// Row by row from the top, alternating between going right and left.
+    Snake,
+    /// Clockwise from the top-left corner, spiraling inwards.
+    Spiral,
+    /// Following a Hilbert curve from the top-left corner, which keeps
+    /// consecutive positions close to each other.
+    ///
+    /// It's generalized to rectangles of any size, which may need a single
+    /// diagonal step when one side is odd and the other one even.
+    Hilbert,
+}
+
+/// Returns the offset of the position at index `i` of a `w` by `h` zone.
+const fn offset(order: PositionOrder, w: u128, h: u128, i: u128) -> (u128, u128) {
+    match order {
+        PositionOrder::RowMajor => (i % w, i / w),
+        PositionOrder::ColumnMajor => (i / h, i % h),
+        PositionOrder::Snake => {
+            let (x, y) = (i % w, i / w);
+            (if y % 2 == 0 { x } else { w - 1 - x }, y)
+        }
+        PositionOrder::Spiral => spiral(w, h, i),
+        PositionOrder::Hilbert => hilbert(w, h, i),
+    }
+}
+
+/// Returns the offset of the position at index `i` of a spiral.
+const fn spiral(w: u128, h: u128, i: u128) -> (u128, u128) {
+    // the number of positions in the rings outside ring `r`
+    const fn outside(w: u128, h: u128, r: u128) -> u128 {
+        2 * r * (w + h) - 4 * r * r
+    }
+    // the last ring starting at or before `i`
+    let (mut lo, mut hi) = (0, ((if w < h { w } else { h }) - 1) / 2);
+    while lo < hi {
+        let mid = (lo + hi + 1) / 2;
+        if outside(w, h, mid) <= i {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+    let (r, t) = (lo, i - outside(w, h, lo));
+    let (rw, rh) = (w - 2 * r, h - 2 * r);
+
+    if t < rw {
+        (r + t, r)
+    } else if t < rw + rh - 1 {
+        (r + rw - 1, r + 1 + t - rw)
+    } else if t < 2 * rw + rh - 2 {
+        (r + rw - 2 - (t + 1 - rw - rh), r + rh - 1)
+    } else {
+        (r, r + rh - 2 - (t + 2 - 2 * rw - rh))
+    }
+}
+
+/// Returns the offset of the position at index `i` of a generalized Hilbert
+/// curve covering a `w` by `h` zone.
+///
+/// It follows the *gilbert* algorithm, which splits the zone along the curve
+/// in two or three smaller ones, down to a single row or column.
+const fn hilbert(w: u128, h: u128, i: u128) -> (u128, u128) {
+    // the current part starts at `x, y`, and spans the major axis `a`
+    // and the minor axis `b`, with `i` being the index inside of it
+    let (mut x, mut y, mut i) = (0_i128, 0_i128, i as i128);
+    let (mut ax, mut ay, mut bx, mut by) = if w >= h {
+        (w as i128, 0, 0, h as i128)
+    } else {
+        (0, h as i128, w as i128, 0)
+    };
+    loop {
+        let (w, h) = ((ax + ay).abs(), (bx + by).abs());
+        let (dax, day, dbx, dby) = (ax.signum(), ay.signum(), bx.signum(), by.signum());
+        if h == 1 {
+            return ((x + dax * i) as u128, (y + day * i) as u128);
+        } else if w == 1 {
+            return ((x + dbx * i) as u128, (y + dby * i) as u128);
+        }
+
+        let (mut ax2, mut ay2) = (ax.div_euclid(2), ay.div_euclid(2));
+        let (mut bx2, mut by2) = (bx.div_euclid(2), by.div_euclid(2));
+        if 2 * w > 3 * h {
+            // a long part is split in two halves, preferring even ones
+            if (ax2 + ay2).abs() % 2 == 1 && w > 2 {
+                ax2 += dax;
+                ay2 += day;
+            }
+            let first = ((ax2 + ay2) * (bx + by)).abs();
+            if i < first {
+                (ax, ay) = (ax2, ay2);
+            } else {
+                i -= first;
+                (x, y) = (x + ax2, y + ay2);
+                (ax, ay) = (ax - ax2, ay - ay2);
+            }
+        } else {
+            // otherwise it goes up, along, and back down, preferring even steps
+            if (bx2 + by2).abs() % 2 == 1 && h > 2 {
+                bx2 += dbx;
+                by2 += dby;
+            }
+            let first = ((bx2 + by2) * (ax2 + ay2)).abs();
+            let second = ((ax + ay) * (bx - bx2 + by - by2)).abs();
+            if i < first {
+                (ax, ay, bx, by) = (bx2, by2, ax2, ay2);
+            } else if i < first + second {
+                i -= first;
+                (x, y) = (x + bx2, y + by2);
+                (bx, by) = (bx - bx2, by - by2);
+            } else {
+                i -= first + second;
+                x += (ax - dax) + (bx2 - dbx);
+                y += (ay - day) + (by2 - dby);
+                (ax, ay, bx, by) = (-bx2, -by2, -(ax - ax2), -(ay - ay2));
+            }
+        }
+    }
+}
+
+macro_rules! positions {
+    // $i: inner primitive
+    // $b: bit size
+    ( $($i:ty, $b:expr),+ ) => {
+        $( positions![single: $i, $b]; )+
+    };
+
+    (single: $i:ty, $b:literal) => { paste::paste! {
+        use super::{[<Clamper$b>] as [<C$b>], [<Position$b>], [<Zone$b>]};
+
+        #[doc = "An iterator over the positions of a [`Zone" $b "`]."]
+        ///
+        /// Only the positions that can be represented are visited, which
+        /// excludes the part of a zone reaching beyond the clamped maximum.
+        ///
+        /// The exact length is only reported while it fits in a `usize`.
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub struct [<ZonePositions$b>] {
+            order: PositionOrder,
+            x: $i,
+            y: $i,
+            w: u128,
+            h: u128,
+            /// The next index from the front.
+            front: u128,
+            /// The next index from the back, exclusive.
+            back: u128,
+        }
+
+        impl [<ZonePositions$b>] {
+            const fn new(zone: &[<Zone$b>], order: PositionOrder) -> Self {
+                let (x, y) = (zone.x(), zone.y());
+                let (max_w, max_h) = ([<C$b>]::MAX as i128 - x as i128 + 1, [<C$b>]::MAX as i128 - y as i128 + 1);
+                let w = if (zone.w() as i128) < max_w { zone.w() as u128 } else { max_w as u128 };
+                let h = if (zone.h() as i128) < max_h { zone.h() as u128 } else { max_h as u128 };
+                Self { order, x, y, w, h, front: 0, back: w * h }
+            }
+
+            fn at(&self, i: u128) -> [<Position$b>] {
+                let (dx, dy) = offset(self.order, self.w, self.h, i);
+                [<Position$b>]::new(self.x + dx as $i, self.y + dy as $i)
+            }
+        }
+
+        impl Iterator for [<ZonePositions$b>] {
+            type Item = [<Position$b>];
+
+            fn next(&mut self) -> Option<Self::Item> {
+                if self.front == self.back {
+                    return None;
+                }
+                self.front += 1;
+                Some(self.at(self.front - 1))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                match usize::try_from(self.back - self.front) {
+                    Ok(n) => (n, Some(n)),
+                    Err(_) => (usize::MAX, None),
+                }
+            }
+        }
+
+        impl DoubleEndedIterator for [<ZonePositions$b>] {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                if self.front == self.back {
+                    return None;
+                }
+                self.back -= 1;
+                Some(self.at(self.back))
+            }
+        }
+
+        impl FusedIterator for [<ZonePositions$b>] {}
+
+        /// # positions
+        impl [<Zone$b>] {
+            /// Returns an iterator over the positions inside the zone,
+            /// in row-major order.
+            pub const fn positions(&self) -> [<ZonePositions$b>] {
+                [<ZonePositions$b>]::new(self, PositionOrder::RowMajor)
+            }
+            /// Returns an iterator over the positions inside the zone,
+            /// in the given `order`.
+            pub const fn positions_by(&self, order: PositionOrder) -> [<ZonePositions$b>] {
+                [<ZonePositions$b>]::new(self, order)
+            }
+        }
+    }};
+}
+positions![i8, 8, i16, 16, i32, 32, i64, 64];
+
+// the wider zones can have more positions than fit in a usize
+impl ExactSizeIterator for ZonePositions8 {}
+impl ExactSizeIterator for ZonePositions16 {}
@@ -0,0 +1,161 @@
+// cuadra::raster::bezier
+//
+//! Bezier curves.
+//
+
+use crate::{Line32, Position32};
+use core::iter::FusedIterator;
+
+/// The number of fractional bits of the fixed-point coordinates.
+const FRAC: u32 = 16;
+
+/// The maximum number of times a curve is subdivided.
+const MAX_DEPTH: u8 = 16;
+
+/// A point with fixed-point coordinates.
+type Point = (i64, i64);
+
+/// A cubic segment of the curve being flattened.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Cubic {
+    p: [Point; 4],
+    depth: u8,
+}
+
+impl Cubic {
+    /// Returns `true` if the control points are close enough to the chord,
+    /// given the squared tolerance times `16`.
+    fn is_flat(&self, tolerance: i128) -> bool {
+        let [p0, p1, p2, p3] = self.p;
+        let sq = |v: i64| (v as i128) * (v as i128);
+        let ux = sq(3 * p1.0 - 2 * p0.0 - p3.0);
+        let uy = sq(3 * p1.1 - 2 * p0.1 - p3.1);
+        let vx = sq(3 * p2.0 - p0.0 - 2 * p3.0);
+        let vy = sq(3 * p2.1 - p0.1 - 2 * p3.1);
+        ux.max(vx) + uy.max(vy) <= tolerance
+    }
+
+    /// Splits the curve in two halves.
+    fn split(&self) -> (Cubic, Cubic) {
+        let mid = |a: Point, b: Point| ((a.0 + b.0) >> 1, (a.1 + b.1) >> 1);
+        let [p0, p1, p2, p3] = self.p;
+        let (p01, p12, p23) = (mid(p0, p1), mid(p1, p2), mid(p2, p3));
+        let (p012, p123) = (mid(p01, p12), mid(p12, p23));
+        let m = mid(p012, p123);
+        let depth = self.depth + 1;
+        (
+            Cubic {
+                p: [p0, p01, p012, m],
+                depth,
+            },
+            Cubic {
+                p: [m, p123, p23, p3],
+                depth,
+            },
+        )
+    }
+}
+
+const fn to_fixed(p: Position32) -> Point {
+    ((p.x() as i64) << FRAC, (p.y() as i64) << FRAC)
+}
+const fn to_position(p: Point) -> Position32 {
+    let half = 1 << (FRAC - 1);
+    Position32::new(((p.0 + half) >> FRAC) as i32, ((p.1 + half) >> FRAC) as i32)
+}
+
+/// An iterator over the positions of a quadratic or cubic Bezier curve
+/// between [`Position32`]s.
+///
+/// The curve is flattened into lines using only integer arithmetic,
+/// and consecutive positions are always connected, at least diagonally.
+/// It doesn't allocate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bezier32 {
+    /// The segments left to flatten, the next one last.
+    stack: [Cubic; MAX_DEPTH as usize + 1],
+    len: usize,
+    /// The squared tolerance times `16`, in fixed-point units.
+    tolerance: i128,
+    /// The line towards the end of the last flattened segment.
+    line: Line32,
+    last: Position32,
+}
+
+impl Bezier32 {
+    /// Returns a quadratic curve from `start` to `end`, with one `control` point.
+    pub fn quadratic(start: Position32, control: Position32, end: Position32) -> Self {
+        let (p0, c, p3) = (to_fixed(start), to_fixed(control), to_fixed(end));
+        // the equivalent cubic curve
+        let third = |a: Point, b: Point| (a.0 + (b.0 - a.0) * 2 / 3, a.1 + (b.1 - a.1) * 2 / 3);
+        Self::from_points(start, [p0, third(p0, c), third(p3, c), p3])
+    }
+
+    /// Returns a cubic curve from `start` to `end`, with two control points.
+    pub fn cubic(
+        start: Position32,
+        control1: Position32,
+        control2: Position32,
+        end: Position32,
+    ) -> Self {
+        let p = [
+            to_fixed(start),
+            to_fixed(control1),
+            to_fixed(control2),
+            to_fixed(end),
+        ];
+        Self::from_points(start, p)
+    }
+
+    fn from_points(start: Position32, p: [Point; 4]) -> Self {
+        let mut stack = [Cubic::default(); MAX_DEPTH as usize + 1];
+        stack[0] = Cubic { p, depth: 0 };
+        Self {
+            stack,
+            len: 1,
+            tolerance: 0,
+            line: Line32::new(start, start),
+            last: start,
+        }
+        .tolerance(64)
+    }
+
+    /// Chain-sets the maximum distance between the curve and its flattened
+    /// lines, in 256ths of a cell. The default is `64`, a quarter of a cell.
+    ///
+    /// Smaller tolerances produce smoother curves, with more lines.
+    pub fn tolerance(mut self, tolerance: u32) -> Self {
+        let t = (tolerance as i128) << (FRAC - 8);
+        self.tolerance = 16 * t * t;
+        self
+    }
+}
+
+impl Iterator for Bezier32 {
+    type Item = Position32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(position) = self.line.next() {
+                return Some(position);
+            }
+            if self.len == 0 {
+                return None;
+            }
+            self.len -= 1;
+            let curve = self.stack[self.len];
+            if curve.depth >= MAX_DEPTH || curve.is_flat(self.tolerance) {
+                let end = to_position(curve.p[3]);
+                self.line = Line32::new(self.last, end).skip_start();
+                self.last = end;
+            } else {
+                let (first, second) = curve.split();
+                self.stack[self.len] = second;
+                self.stack[self.len + 1] = first;
+                self.len += 2;
+            }
+        }
+    }
+}
+
+impl FusedIterator for Bezier32 {}
@@ -0,0 +1,218 @@
+// cuadra::raster::ellipse
+//
+//! Circles and ellipses.
+//
+
+use core::iter::FusedIterator;
+
+/// Returns `true` if the offset `x, y` is inside an ellipse with the given radii.
+///
+/// It's the midpoint criterion, which compares against radii half a cell
+/// longer, so that the outline looks the same as the midpoint algorithm's.
+const fn inside(rx: u128, ry: u128, x: u128, y: u128) -> bool {
+    let (a, b) = ((2 * ry + 1) * (2 * ry + 1), (2 * rx + 1) * (2 * rx + 1));
+    4 * x * x * a + 4 * y * y * b <= a * b
+}
+
+/// Returns the greatest horizontal offset inside an ellipse with the given radii,
+/// at the vertical offset `y`, which must be at most `ry`.
+const fn half_width(rx: u128, ry: u128, y: u128) -> u128 {
+    let (mut lo, mut hi) = (0, rx);
+    while lo < hi {
+        let mid = (lo + hi + 1) / 2;
+        if inside(rx, ry, mid, y) {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+    lo
+}
+
+macro_rules! ellipse {
+    // $i: inner primitive
+    // $b: bit size
+    ( $($i:ty, $b:expr),+ ) => {
+        $( ellipse![single: $i, $b]; )+
+    };
+
+    (single: $i:ty, $b:literal) => { paste::paste! {
+        use crate::{[<Clamper$b>] as [<C$b>], [<Position$b>], [<Size$b>], [<Zone$b>]};
+
+        #[doc = "An ellipse centered on a [`Position" $b "`], with a radius for each axis."]
+        ///
+        /// A radius of `0` makes it a single cell thick along that axis.
+        /// The parts beyond the clamped range are clamped.
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+        pub struct [<Ellipse$b>] {
+            center: [<Position$b>],
+            rx: $i,
+            ry: $i,
+        }
+
+        impl [<Ellipse$b>] {
+            /// Returns a new ellipse with the width and height of `radii` as its radii.
+            pub const fn new(center: [<Position$b>], radii: [<Size$b>]) -> Self {
+                Self { center, rx: radii.w(), ry: radii.h() }
+            }
+            /// Returns a new circle with the given non-negative `radius`.
+            pub const fn circle(center: [<Position$b>], radius: $i) -> Self {
+                let r = [<C$b>]::clamp_non_negative(radius);
+                Self { center, rx: r, ry: r }
+            }
+
+            /// Returns the center.
+            pub const fn center(&self) -> [<Position$b>] {
+                self.center
+            }
+            /// Returns the horizontal and vertical radii.
+            pub const fn radii(&self) -> ($i, $i) {
+                (self.rx, self.ry)
+            }
+
+            /// Returns `true` if the `position` is inside the ellipse.
+            pub const fn contains_position(&self, position: [<Position$b>]) -> bool {
+                let x = (position.x() as i128 - self.center.x() as i128).unsigned_abs();
+                let y = (position.y() as i128 - self.center.y() as i128).unsigned_abs();
+                x <= self.rx as u128 && y <= self.ry as u128
+                    && inside(self.rx as u128, self.ry as u128, x, y)
+            }
+
+            /// Returns an iterator over the positions of the outline,
+            /// row by row from the top, each one from left to right.
+            ///
+            /// Consecutive rows are always connected, at least diagonally.
+            pub fn outline(&self) -> [<EllipseOutline$b>] {
+                let mut outline = [<EllipseOutline$b>] {
+                    ellipse: *self,
+                    y: -(self.ry as i128),
+                    dx: 0,
+                    hw: 0,
+                    inner: 0,
+                };
+                outline.row();
+                outline
+            }
+
+            /// Returns an iterator over the horizontal spans filling the ellipse,
+            /// as zones with a height of `1`, from the top.
+            pub const fn spans(&self) -> [<EllipseSpans$b>] {
+                [<EllipseSpans$b>] {
+                    ellipse: *self,
+                    y: -(self.ry as i128),
+                    y_end: self.ry as i128 + 1,
+                }
+            }
+
+            /// Returns the greatest horizontal offset at the vertical offset `y`.
+            const fn half_width(&self, y: i128) -> i128 {
+                half_width(self.rx as u128, self.ry as u128, y.unsigned_abs()) as i128
+            }
+            /// Returns the span at the vertical offset `y`.
+            const fn span(&self, y: i128) -> [<Zone$b>] {
+                let hw = self.half_width(y);
+                let (cx, cy) = (self.center.x() as i128, self.center.y() as i128);
+                let x = [<C$b>]::clamp((cx - hw) as $i);
+                let w = cx + hw + 1 - x as i128;
+                let w = if w > [<C$b>]::MAX as i128 { [<C$b>]::MAX } else { w as $i };
+                [<Zone$b>]::new_raw(x, (cy + y) as $i, w, 1)
+            }
+        }
+
+        #[doc = "An iterator over the outline of an [`Ellipse" $b "`]."]
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub struct [<EllipseOutline$b>] {
+            ellipse: [<Ellipse$b>],
+            /// The vertical offset of the current row.
+            y: i128,
+            /// The next horizontal offset.
+            dx: i128,
+            /// The half width of the current row.
+            hw: i128,
+            /// The half width of the part of the current row that's not outline.
+            inner: i128,
+        }
+
+        impl [<EllipseOutline$b>] {
+            /// Prepares the current row.
+            fn row(&mut self) {
+                let ry = self.ellipse.ry as i128;
+                self.hw = self.ellipse.half_width(self.y);
+                self.inner = if self.y.abs() == ry {
+                    -1
+                } else {
+                    // the half width of the next row outwards
+                    let outer = self.ellipse.half_width(self.y.abs() + 1);
+                    if outer < self.hw { outer } else { self.hw - 1 }
+                };
+                self.dx = -self.hw;
+            }
+        }
+
+        impl Iterator for [<EllipseOutline$b>] {
+            type Item = [<Position$b>];
+
+            fn next(&mut self) -> Option<Self::Item> {
+                if self.dx > self.hw {
+                    if self.y >= self.ellipse.ry as i128 {
+                        return None;
+                    }
+                    self.y += 1;
+                    self.row();
+                }
+                let (cx, cy) = (self.ellipse.center.x() as i128, self.ellipse.center.y() as i128);
+                let position = [<Position$b>]::new((cx + self.dx) as $i, (cy + self.y) as $i);
+                // jump over the inner part of the row
+                if self.inner >= 0 && self.dx == -(self.inner + 1) {
+                    self.dx = self.inner + 1;
+                } else {
+                    self.dx += 1;
+                }
+                Some(position)
+            }
+        }
+
+        impl FusedIterator for [<EllipseOutline$b>] {}
+
+        #[doc = "An iterator over the horizontal spans filling an [`Ellipse" $b "`]."]
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub struct [<EllipseSpans$b>] {
+            ellipse: [<Ellipse$b>],
+            /// The vertical offset of the next row from the top.
+            y: i128,
+            /// The vertical offset after the next row from the bottom.
+            y_end: i128,
+        }
+
+        impl Iterator for [<EllipseSpans$b>] {
+            type Item = [<Zone$b>];
+
+            fn next(&mut self) -> Option<Self::Item> {
+                if self.y >= self.y_end {
+                    return None;
+                }
+                self.y += 1;
+                Some(self.ellipse.span(self.y - 1))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let n = (self.y_end - self.y) as usize;
+                (n, Some(n))
+            }
+        }
+
+        impl DoubleEndedIterator for [<EllipseSpans$b>] {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                if self.y >= self.y_end {
+                    return None;
+                }
+                self.y_end -= 1;
+                Some(self.ellipse.span(self.y_end))
+            }
+        }
+
+        impl ExactSizeIterator for [<EllipseSpans$b>] {}
+        impl FusedIterator for [<EllipseSpans$b>] {}
+    }};
+}
+ellipse![i8, 8, i16, 16, i32, 32];
@@ -0,0 +1,172 @@
+// cuadra::raster::line
+//
+//! Lines between positions.
+//
+
+use core::iter::FusedIterator;
+
+macro_rules! line {
+    // $i: inner primitive
+    // $b: bit size
+    // $w: wider primitive
+    ( $($i:ty, $b:expr, $w:ty),+ ) => {
+        $( line![single: $i, $b, $w]; )+
+    };
+
+    (single: $i:ty, $b:literal, $w:ty) => { paste::paste! {
+        use crate::[<Position$b>];
+
+        #[doc = "An iterator over the positions of a line between two [`Position" $b "`]s."]
+        ///
+        /// It includes both endpoints by default. The wider primitive used for
+        /// the arithmetic, together with the clamped range, ensures that no
+        /// intermediate step can overflow.
+        ///
+        /// The exact length is only reported while it fits in a `usize`.
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub struct [<Line$b>] {
+            x: $w,
+            y: $w,
+            sx: $w,
+            sy: $w,
+            nx: $w,
+            ny: $w,
+            /// The error term of an 8-connected line.
+            err: $w,
+            /// The steps taken along each axis by a 4-connected line.
+            ix: $w,
+            iy: $w,
+            four: bool,
+            /// The index of the current position.
+            index: $w,
+            /// The index after the last position to yield.
+            end: $w,
+        }
+
+        impl [<Line$b>] {
+            /// Returns an 8-connected line, where consecutive positions can be diagonal.
+            pub const fn new(from: [<Position$b>], to: [<Position$b>]) -> Self {
+                Self::with_connectivity(from, to, false)
+            }
+            /// Returns a 4-connected line, where consecutive positions always share an edge.
+            ///
+            /// Where the line crosses exactly through a corner, the horizontal step goes first.
+            pub const fn new4(from: [<Position$b>], to: [<Position$b>]) -> Self {
+                Self::with_connectivity(from, to, true)
+            }
+
+            const fn with_connectivity(from: [<Position$b>], to: [<Position$b>], four: bool) -> Self {
+                let (x, y) = (from.x() as $w, from.y() as $w);
+                let (dx, dy) = (to.x() as $w - x, to.y() as $w - y);
+                let (nx, ny) = (dx.abs(), dy.abs());
+                let steps = if four { nx + ny } else if nx > ny { nx } else { ny };
+                Self {
+                    x,
+                    y,
+                    sx: dx.signum(),
+                    sy: dy.signum(),
+                    nx,
+                    ny,
+                    err: nx - ny,
+                    ix: 0,
+                    iy: 0,
+                    four,
+                    index: 0,
+                    end: steps + 1,
+                }
+            }
+
+            /// Chain-sets the start position to be skipped.
+            pub fn skip_start(mut self) -> Self {
+                if self.index == 0 && self.end > 0 {
+                    self.advance();
+                }
+                self
+            }
+            /// Chain-sets the end position to be skipped.
+            pub const fn skip_end(mut self) -> Self {
+                let steps = if self.four {
+                    self.nx + self.ny
+                } else if self.nx > self.ny {
+                    self.nx
+                } else {
+                    self.ny
+                };
+                if self.end > steps {
+                    self.end = steps;
+                }
+                self
+            }
+
+            /// Moves to the next position.
+            fn advance(&mut self) {
+                self.index += 1;
+                if self.four {
+                    if (1 + 2 * self.ix) * self.ny <= (1 + 2 * self.iy) * self.nx {
+                        self.ix += 1;
+                        self.x += self.sx;
+                    } else {
+                        self.iy += 1;
+                        self.y += self.sy;
+                    }
+                } else {
+                    let e2 = 2 * self.err;
+                    if e2 > -self.ny {
+                        self.err -= self.ny;
+                        self.x += self.sx;
+                    }
+                    if e2 < self.nx {
+                        self.err += self.nx;
+                        self.y += self.sy;
+                    }
+                }
+            }
+        }
+
+        impl Iterator for [<Line$b>] {
+            type Item = [<Position$b>];
+
+            fn next(&mut self) -> Option<Self::Item> {
+                if self.index >= self.end {
+                    return None;
+                }
+                let position = [<Position$b>]::new(self.x as $i, self.y as $i);
+                if self.index + 1 < self.end {
+                    self.advance();
+                } else {
+                    self.index += 1;
+                }
+                Some(position)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let n = if self.end > self.index { (self.end - self.index) as u128 } else { 0 };
+                match usize::try_from(n) {
+                    Ok(n) => (n, Some(n)),
+                    Err(_) => (usize::MAX, None),
+                }
+            }
+        }
+
+        impl FusedIterator for [<Line$b>] {}
+
+        /// # lines
+        impl [<Position$b>] {
+            /// Returns an iterator over the positions of an 8-connected line
+            /// from this position to the `other`.
+            pub const fn line_to(&self, other: [<Position$b>]) -> [<Line$b>] {
+                [<Line$b>]::new(*self, other)
+            }
+            /// Returns an iterator over the positions of a 4-connected line
+            /// from this position to the `other`.
+            pub const fn line4_to(&self, other: [<Position$b>]) -> [<Line$b>] {
+                [<Line$b>]::new4(*self, other)
+            }
+        }
+    }};
+}
+line![i8, 8, i16, i16, 16, i32, i32, 32, i64, i64, 64, i128];
+
+// the wider lines can have more positions than fit in a usize
+impl ExactSizeIterator for Line8 {}
+impl ExactSizeIterator for Line16 {}
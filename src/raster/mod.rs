@@ -0,0 +1,19 @@
+// cuadra::raster
+//
+//! Rasterization of shapes into positions.
+//
+
+mod bezier;
+mod ellipse;
+mod line;
+#[cfg(feature = "alloc")]
+mod polygon;
+
+pub use bezier::Bezier32;
+pub use ellipse::{
+    Ellipse16, Ellipse32, Ellipse8, EllipseOutline16, EllipseOutline32, EllipseOutline8,
+    EllipseSpans16, EllipseSpans32, EllipseSpans8,
+};
+pub use line::{Line16, Line32, Line64, Line8};
+#[cfg(feature = "alloc")]
+pub use polygon::{FillRule, PolygonSpans32};
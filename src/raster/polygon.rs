@@ -0,0 +1,187 @@
+// cuadra::raster::polygon
+//
+//! Polygon filling.
+//
+
+use crate::{Position32, Zone32};
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// The rule deciding which parts of a self-intersecting polygon are inside.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FillRule {
+    /// Inside where a ray crosses the outline an odd number of times.
+    #[default]
+    EvenOdd,
+    /// Inside where the outline winds around a non-zero number of times.
+    NonZero,
+}
+
+/// A non-horizontal edge, oriented downwards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Edge {
+    x0: i64,
+    y0: i64,
+    x1: i64,
+    y1: i64,
+    /// `1` if the original edge went downwards, `-1` otherwise.
+    winding: i8,
+}
+
+impl Edge {
+    /// Returns the first cell at or after the edge, in the row `y`.
+    ///
+    /// That's the first one whose center is at or after the crossing
+    /// of the edge with the horizontal line through the cell centers.
+    fn crossing(&self, y: i64) -> i64 {
+        let (dx, dy) = ((self.x1 - self.x0) as i128, (self.y1 - self.y0) as i128);
+        // ceil(x0 + (y + 0.5 - y0) * dx / dy - 0.5)
+        let n = (2 * self.x0 as i128 - 1) * dy + (2 * (y - self.y0) as i128 + 1) * dx;
+        -((-n).div_euclid(2 * dy)) as i64
+    }
+}
+
+/// An iterator over the horizontal spans filling a polygon, as [`Zone32`]s
+/// with a height of `1`, row by row from the top, each one from left to right.
+///
+/// The vertices are the corners of the cells, so the polygon with vertices
+/// `(0, 0)`, `(4, 0)`, `(4, 3)` and `(0, 3)` fills a zone of size `4, 3`.
+/// A cell is filled if its center is inside the polygon, and cells whose
+/// center is exactly on an edge are only filled if the inside is to their right.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PolygonSpans32 {
+    rule: FillRule,
+    clip: Option<Zone32>,
+    /// The edges, sorted by their top.
+    edges: Vec<Edge>,
+    /// The next edge to become active.
+    next_edge: usize,
+    /// The edges crossing the current row.
+    active: Vec<usize>,
+    /// The current row.
+    y: i64,
+    y_end: i64,
+    /// The spans of the current row, reversed.
+    spans: Vec<(i64, i64)>,
+}
+
+impl PolygonSpans32 {
+    /// Returns an iterator over the spans filling the polygon with the
+    /// given `vertices`, using the given fill `rule`.
+    ///
+    /// The polygon is closed from the last vertex back to the first.
+    pub fn new(vertices: &[Position32], rule: FillRule) -> Self {
+        let mut edges: Vec<Edge> = Vec::with_capacity(vertices.len());
+        for (i, a) in vertices.iter().enumerate() {
+            let b = vertices[(i + 1) % vertices.len()];
+            let (a, b) = ((a.x() as i64, a.y() as i64), (b.x() as i64, b.y() as i64));
+            if a.1 != b.1 {
+                let (top, bottom, winding) = if a.1 < b.1 { (a, b, 1) } else { (b, a, -1) };
+                edges.push(Edge {
+                    x0: top.0,
+                    y0: top.1,
+                    x1: bottom.0,
+                    y1: bottom.1,
+                    winding,
+                });
+            }
+        }
+        edges.sort_by_key(|e| e.y0);
+        let y = edges.first().map_or(0, |e| e.y0);
+        let y_end = edges.iter().map(|e| e.y1).max().unwrap_or(0);
+        Self {
+            rule,
+            clip: None,
+            edges,
+            next_edge: 0,
+            active: Vec::new(),
+            y,
+            y_end,
+            spans: Vec::new(),
+        }
+    }
+
+    /// Chain-sets the zone the spans are clipped to.
+    pub fn clip(mut self, zone: Zone32) -> Self {
+        self.y = self.y.max(zone.y() as i64);
+        self.y_end = self.y_end.min(zone.y_end() as i64);
+        self.clip = Some(zone);
+        self
+    }
+
+    /// Computes the spans of the current row.
+    fn scan(&mut self) {
+        let y = self.y;
+        while self.edges.get(self.next_edge).map_or(false, |e| e.y0 <= y) {
+            self.active.push(self.next_edge);
+            self.next_edge += 1;
+        }
+        let edges = &self.edges;
+        self.active.retain(|&e| y < edges[e].y1);
+
+        let mut crossings: Vec<(i64, i8)> = self
+            .active
+            .iter()
+            .map(|&e| (edges[e].crossing(y), edges[e].winding))
+            .collect();
+        crossings.sort_unstable();
+
+        let (x_min, x_max) = match self.clip {
+            Some(c) => (c.x() as i64, c.x_end() as i64),
+            None => (i64::MIN, i64::MAX),
+        };
+        let push = |spans: &mut Vec<(i64, i64)>, x: i64, x_end: i64| {
+            let (x, x_end) = (x.max(x_min), x_end.min(x_max));
+            if x >= x_end {
+                return;
+            }
+            match spans.last_mut() {
+                Some(last) if last.1 >= x => last.1 = last.1.max(x_end),
+                _ => spans.push((x, x_end)),
+            }
+        };
+        self.spans.clear();
+        let (mut winding, mut start) = (0_i32, 0);
+        for (i, &(x, w)) in crossings.iter().enumerate() {
+            let was_inside = match self.rule {
+                FillRule::EvenOdd => i % 2 == 1,
+                FillRule::NonZero => winding != 0,
+            };
+            winding += w as i32;
+            let inside = match self.rule {
+                FillRule::EvenOdd => i % 2 == 0,
+                FillRule::NonZero => winding != 0,
+            };
+            if !was_inside && inside {
+                start = x;
+            } else if was_inside && !inside {
+                push(&mut self.spans, start, x);
+            }
+        }
+        self.spans.reverse();
+    }
+}
+
+impl Iterator for PolygonSpans32 {
+    type Item = Zone32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((x, x_end)) = self.spans.pop() {
+                return Some(Zone32::new_raw(
+                    x as i32,
+                    self.y as i32 - 1,
+                    (x_end - x) as i32,
+                    1,
+                ));
+            }
+            if self.y >= self.y_end {
+                return None;
+            }
+            self.scan();
+            self.y += 1;
+        }
+    }
+}
+
+impl FusedIterator for PolygonSpans32 {}
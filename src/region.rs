@@ -0,0 +1,257 @@
+// cuadra::region
+//
+//! Rectilinear regions.
+//
+
+use crate::{Clamper32, Position32, Zone32};
+use alloc::vec::Vec;
+
+/// A horizontal span of cells, as `(x, x_end)`.
+type Span = (i32, i32);
+
+/// A horizontal band of rows, covered by the same spans.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Band {
+    y: i32,
+    y_end: i32,
+    /// The sorted, disjoint and non-adjacent spans.
+    spans: Vec<Span>,
+}
+
+/// An arbitrary rectilinear area, made up of [`Zone32`]s.
+///
+/// It's stored in a normalized form, as a list of horizontal bands that don't
+/// overlap, each one covered by a list of horizontal spans, and where adjacent
+/// bands always have different spans. So equal areas always compare as equal.
+///
+/// The rectangles of a region reaching beyond the clamped maximum range of a
+/// `Zone32` are clamped when returned as zones.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Region32 {
+    bands: Vec<Band>,
+}
+
+impl From<Zone32> for Region32 {
+    fn from(zone: Zone32) -> Self {
+        Self::from_zone(zone)
+    }
+}
+
+impl FromIterator<Zone32> for Region32 {
+    /// Returns the union of all the zones.
+    fn from_iter<I: IntoIterator<Item = Zone32>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::new(), |region, zone| {
+            region.union(&Self::from_zone(zone))
+        })
+    }
+}
+
+impl Region32 {
+    /// Returns a new empty region.
+    pub const fn new() -> Self {
+        Self { bands: Vec::new() }
+    }
+
+    /// Returns a new region covering the given `zone`.
+    pub fn from_zone(zone: Zone32) -> Self {
+        Self {
+            bands: Vec::from([Band {
+                y: zone.y(),
+                y_end: zone.y_end(),
+                spans: Vec::from([(zone.x(), zone.x_end())]),
+            }]),
+        }
+    }
+
+    /// Returns `true` if the region is empty.
+    pub fn is_empty(&self) -> bool {
+        self.bands.is_empty()
+    }
+
+    /// Returns the number of cells covered by the region.
+    pub fn area(&self) -> u64 {
+        self.bands
+            .iter()
+            .map(|b| {
+                let w: u64 = b.spans.iter().map(|&(x, x_end)| extent(x, x_end)).sum();
+                w * extent(b.y, b.y_end)
+            })
+            .sum()
+    }
+
+    /// Returns the bounding box of the region, or `None` if it's empty.
+    pub fn bounds(&self) -> Option<Zone32> {
+        let (first, last) = (self.bands.first()?, self.bands.last()?);
+        let x = self.bands.iter().map(|b| b.spans[0].0).min()?;
+        let x_end = self
+            .bands
+            .iter()
+            .map(|b| b.spans[b.spans.len() - 1].1)
+            .max()?;
+        Some(zone(x, first.y, x_end, last.y_end))
+    }
+
+    /// Returns `true` if the given `position` is inside the region.
+    pub fn contains_position(&self, position: Position32) -> bool {
+        let (x, y) = position.as_tuple();
+        self.bands
+            .iter()
+            .find(|b| y < b.y_end)
+            .filter(|b| b.y <= y)
+            .map_or(false, |b| b.spans.iter().any(|&(x0, x1)| x0 <= x && x < x1))
+    }
+
+    /// Returns an iterator over the disjoint zones making up the region,
+    /// from top to bottom and from left to right.
+    pub fn rects(&self) -> impl Iterator<Item = Zone32> + '_ {
+        self.bands.iter().flat_map(|b| {
+            b.spans
+                .iter()
+                .map(move |&(x, x_end)| zone(x, b.y, x_end, b.y_end))
+        })
+    }
+
+    /// Returns the region covering the cells in either region.
+    pub fn union(&self, other: &Region32) -> Region32 {
+        self.combine(other, |a, b| a || b)
+    }
+    /// Returns the region covering the cells in both regions.
+    pub fn intersection(&self, other: &Region32) -> Region32 {
+        self.combine(other, |a, b| a && b)
+    }
+    /// Returns the region covering the cells in this region but not in the `other`.
+    pub fn difference(&self, other: &Region32) -> Region32 {
+        self.combine(other, |a, b| a && !b)
+    }
+    /// Returns the region covering the cells in exactly one of the regions.
+    pub fn xor(&self, other: &Region32) -> Region32 {
+        self.combine(other, |a, b| a != b)
+    }
+
+    /// Returns the region moved by the given offsets.
+    ///
+    /// The edges are clamped to the range covered by the `Zone32`s.
+    pub fn translate(&self, dx: i32, dy: i32) -> Region32 {
+        let clamp = |v: i32, d: i32| {
+            (v as i64 + d as i64).clamp(Clamper32::MIN as i64, Clamper32::MAX as i64 * 2) as i32
+        };
+        let mut region = Region32::new();
+        for b in &self.bands {
+            let mut spans: Vec<Span> = Vec::with_capacity(b.spans.len());
+            for &(x, x_end) in &b.spans {
+                let (x, x_end) = (clamp(x, dx), clamp(x_end, dx));
+                match spans.last_mut() {
+                    _ if x == x_end => (),
+                    Some(last) if last.1 >= x => last.1 = x_end,
+                    _ => spans.push((x, x_end)),
+                }
+            }
+            let (y, y_end) = (clamp(b.y, dy), clamp(b.y_end, dy));
+            if y < y_end {
+                region.push_band(y, y_end, spans);
+            }
+        }
+        region
+    }
+
+    /* private helpers */
+
+    /// Returns the region covering the cells for which `op` returns `true`,
+    /// given whether they're inside each region.
+    fn combine(&self, other: &Region32, op: impl Fn(bool, bool) -> bool) -> Region32 {
+        let mut ys: Vec<i32> = self
+            .bands
+            .iter()
+            .chain(&other.bands)
+            .flat_map(|b| [b.y, b.y_end])
+            .collect();
+        ys.sort_unstable();
+        ys.dedup();
+
+        let mut region = Region32::new();
+        let (mut a, mut b) = (Bands::new(&self.bands), Bands::new(&other.bands));
+        for w in ys.windows(2) {
+            let spans = combine_spans(a.at(w[0]), b.at(w[0]), &op);
+            region.push_band(w[0], w[1], spans);
+        }
+        region
+    }
+
+    /// Appends a band below the others, merging it with the previous one
+    /// if they're adjacent and have the same spans.
+    fn push_band(&mut self, y: i32, y_end: i32, spans: Vec<Span>) {
+        if spans.is_empty() {
+            return;
+        }
+        match self.bands.last_mut() {
+            Some(last) if last.y_end == y && last.spans == spans => last.y_end = y_end,
+            _ => self.bands.push(Band { y, y_end, spans }),
+        }
+    }
+}
+
+/// Returns the length between `start` and `end`, which can exceed an `i32`.
+fn extent(start: i32, end: i32) -> u64 {
+    (end as i64 - start as i64) as u64
+}
+
+/// Returns the zone between the given corners, with its size clamped.
+fn zone(x: i32, y: i32, x_end: i32, y_end: i32) -> Zone32 {
+    let clamp = |start, end| extent(start, end).min(Clamper32::MAX as u64) as i32;
+    Zone32::new_raw(x, y, clamp(x, x_end), clamp(y, y_end))
+}
+
+/// A cursor over sorted bands, for increasing rows.
+struct Bands<'a> {
+    bands: &'a [Band],
+    next: usize,
+}
+
+impl<'a> Bands<'a> {
+    fn new(bands: &'a [Band]) -> Self {
+        Self { bands, next: 0 }
+    }
+    /// Returns the spans at row `y`, which must not decrease between calls.
+    fn at(&mut self, y: i32) -> &'a [Span] {
+        while self.bands.get(self.next).map_or(false, |b| b.y_end <= y) {
+            self.next += 1;
+        }
+        match self.bands.get(self.next) {
+            Some(b) if b.y <= y => &b.spans,
+            _ => &[],
+        }
+    }
+}
+
+/// Returns the spans covering the cells for which `op` returns `true`,
+/// given whether they're inside each list of spans.
+fn combine_spans(a: &[Span], b: &[Span], op: impl Fn(bool, bool) -> bool) -> Vec<Span> {
+    let mut xs: Vec<i32> = a
+        .iter()
+        .chain(b)
+        .flat_map(|&(x, x_end)| [x, x_end])
+        .collect();
+    xs.sort_unstable();
+    xs.dedup();
+
+    let mut spans: Vec<Span> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    for w in xs.windows(2) {
+        let (x, x_end) = (w[0], w[1]);
+        while a.get(i).map_or(false, |s| s.1 <= x) {
+            i += 1;
+        }
+        while b.get(j).map_or(false, |s| s.1 <= x) {
+            j += 1;
+        }
+        let in_a = a.get(i).map_or(false, |s| s.0 <= x);
+        let in_b = b.get(j).map_or(false, |s| s.0 <= x);
+        if op(in_a, in_b) {
+            match spans.last_mut() {
+                Some(last) if last.1 == x => last.1 = x_end,
+                _ => spans.push((x, x_end)),
+            }
+        }
+    }
+    spans
+}
@@ -0,0 +1,75 @@
+// cuadra::serialize
+//
+//! Serde support.
+//
+// The types are serialized in struct form, like `{ x: 1, y: 2 }`, and
+// deserialized through their clamping constructors.
+
+use crate::{Coord, Position, Size, Zone};
+use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Deserialize)]
+#[serde(rename = "Position")]
+struct RawPosition<T> {
+    x: T,
+    y: T,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Size")]
+struct RawSize<T> {
+    w: T,
+    h: T,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Zone", bound = "T: Coord + Deserialize<'de>")]
+struct RawZone<T> {
+    p: Position<T>,
+    s: Size<T>,
+}
+
+impl<T: Coord + Serialize> Serialize for Position<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Position", 2)?;
+        state.serialize_field("x", &self.x())?;
+        state.serialize_field("y", &self.y())?;
+        state.end()
+    }
+}
+impl<'de, T: Coord + Deserialize<'de>> Deserialize<'de> for Position<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawPosition::deserialize(deserializer)?;
+        Ok(Position::from_xy(raw.x, raw.y))
+    }
+}
+
+impl<T: Coord + Serialize> Serialize for Size<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Size", 2)?;
+        state.serialize_field("w", &self.w())?;
+        state.serialize_field("h", &self.h())?;
+        state.end()
+    }
+}
+impl<'de, T: Coord + Deserialize<'de>> Deserialize<'de> for Size<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawSize::deserialize(deserializer)?;
+        Ok(Size::from_wh(raw.w, raw.h))
+    }
+}
+
+impl<T: Coord + Serialize> Serialize for Zone<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Zone", 2)?;
+        state.serialize_field("p", &self.p)?;
+        state.serialize_field("s", &self.s)?;
+        state.end()
+    }
+}
+impl<'de, T: Coord + Deserialize<'de>> Deserialize<'de> for Zone<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawZone::deserialize(deserializer)?;
+        Ok(Zone { p: raw.p, s: raw.s })
+    }
+}
@@ -1,13 +1,73 @@
 // cuadra::size
 //
-//!
+//! 2D sizes.
 //
 
+use crate::Coord;
 use core::{
     fmt,
     ops::{Add, Div, Mul, Sub},
 };
 
+/// A 2D size using positive clamped [`Coord`]s.
+///
+/// The `w, h` components are clamped to be at least `1`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Size<T> {
+    w: T,
+    h: T,
+}
+
+impl<T: Coord> fmt::Debug for Size<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Size{} {{ w: {}, h: {} }}", T::BITS, self.w, self.h,)
+    }
+}
+
+impl<T: Coord> fmt::Display for Size<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "w:{} h:{}", self.w, self.h)
+    }
+}
+
+impl<T: Coord> Size<T> {
+    /// Defines a new `Size` with the given dimensions, for any [`Coord`],
+    /// which has to be at least `1`.
+    pub fn from_wh(width: T, height: T) -> Self {
+        Self {
+            w: width.clamp_positive(),
+            h: height.clamp_positive(),
+        }
+    }
+
+    /// Get the width.
+    #[inline]
+    pub const fn w(&self) -> T {
+        self.w
+    }
+    /// Get the height.
+    #[inline]
+    pub const fn h(&self) -> T {
+        self.h
+    }
+
+    /// Set the width.
+    #[inline]
+    pub fn set_w(&mut self, width: T) {
+        self.w = width.clamp_positive();
+    }
+    /// Set the height.
+    #[inline]
+    pub fn set_h(&mut self, height: T) {
+        self.h = height.clamp_positive();
+    }
+
+    /// Swaps the `w, h` values.
+    pub fn swap(&mut self) {
+        core::mem::swap(&mut self.h, &mut self.w)
+    }
+}
+
 macro_rules! size {
     // $i: inner primitive
     // $b: bit size
@@ -19,23 +79,7 @@ macro_rules! size {
         use super::[<Clamper$b>] as [<C$b>];
 
         #[doc = "A 2D size using a positive clamped [`" $i "`]."]
-        #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
-        pub struct [<Size$b>] {
-            w: $i,
-            h: $i,
-        }
-
-        impl fmt::Debug for [<Size$b>] {
-            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                write!(f, "{} {{ w: {}, h: {} }}", stringify!([<Size$b>]), self.w, self.h,)
-            }
-        }
-
-        impl fmt::Display for [<Size$b>] {
-            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                write!(f, "w:{} h:{}", self.w, self.h)
-            }
-        }
+        pub type [<Size$b>] = Size<$i>;
 
         impl [<Size$b>] {
             /// Defines a new `Size` with the given dimensions,
@@ -47,33 +91,6 @@ macro_rules! size {
                 }
             }
 
-            /// Get the width.
-            #[inline]
-            pub const fn w(&self) -> $i {
-                self.w
-            }
-            /// Get the height.
-            #[inline]
-            pub const fn h(&self) -> $i {
-                self.h
-            }
-
-            /// Set the width.
-            #[inline]
-            pub fn set_w(&mut self, width: $i) {
-                self.w = [<C$b>]::clamp_positive(width);
-            }
-            /// Set the height.
-            #[inline]
-            pub fn set_h(&mut self, height: $i) {
-                self.h = [<C$b>]::clamp_positive(height);
-            }
-
-            /// Swaps the `w, h` values.
-            pub fn swap(&mut self) {
-                core::mem::swap(&mut self.h, &mut self.w)
-            }
-
             #[doc = "Returns a new `Size"$b  "` with the `w, h` values swapped."]
             pub const fn swapped(&self) -> [<Size$b>] {
                 Self::new(self.h, self.w)
@@ -0,0 +1,274 @@
+// cuadra::spatial::hash
+//
+//! Uniform grid spatial hash.
+//
+
+use crate::{Position32, Size32, Zone32};
+use alloc::{collections::BTreeMap, vec::Vec};
+
+/// The identifier of a value in a [`SpatialHash`].
+///
+/// It stays unique after removal, so a stale identifier can't
+/// remove a later value that happens to reuse its slot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SpatialHashId {
+    index: u32,
+    generation: u32,
+}
+
+/// The metric used to measure a radius.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Metric {
+    /// The greatest of the distances along each axis, covering a square.
+    #[default]
+    Chebyshev,
+    /// The sum of the distances along each axis, covering a diamond.
+    Manhattan,
+}
+
+impl Metric {
+    /// Returns the distance between two positions, using this metric.
+    pub const fn distance(&self, a: Position32, b: Position32) -> u64 {
+        let dx = (a.x() as i64 - b.x() as i64).unsigned_abs();
+        let dy = (a.y() as i64 - b.y() as i64).unsigned_abs();
+        match self {
+            Metric::Chebyshev => {
+                if dx > dy {
+                    dx
+                } else {
+                    dy
+                }
+            }
+            Metric::Manhattan => dx + dy,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Entry<T> {
+    generation: u32,
+    item: Option<(Position32, T)>,
+}
+
+/// Buckets values by their [`Position32`] into cells of a fixed size.
+///
+/// Only the occupied cells are stored. Moving a value only updates
+/// its bucket when it crosses into another cell, which makes it cheap
+/// to update many values every frame.
+#[derive(Clone, Debug)]
+pub struct SpatialHash<T> {
+    cell: Size32,
+    cells: BTreeMap<(i32, i32), Vec<u32>>,
+    entries: Vec<Entry<T>>,
+    vacant: Vec<u32>,
+    len: usize,
+}
+
+impl<T> SpatialHash<T> {
+    /// Returns a new empty spatial hash with cells of the given size.
+    pub fn new(cell: Size32) -> Self {
+        Self {
+            cell,
+            cells: BTreeMap::new(),
+            entries: Vec::new(),
+            vacant: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Returns the size of the cells.
+    pub const fn cell_size(&self) -> Size32 {
+        self.cell
+    }
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Returns `true` if there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts a `value` at the given `position`, and returns its identifier.
+    pub fn insert(&mut self, position: Position32, value: T) -> SpatialHashId {
+        let id = match self.vacant.pop() {
+            Some(index) => {
+                let entry = &mut self.entries[index as usize];
+                entry.item = Some((position, value));
+                SpatialHashId {
+                    index,
+                    generation: entry.generation,
+                }
+            }
+            None => {
+                self.entries.push(Entry {
+                    generation: 0,
+                    item: Some((position, value)),
+                });
+                SpatialHashId {
+                    index: self.entries.len() as u32 - 1,
+                    generation: 0,
+                }
+            }
+        };
+        let cell = self.cell_of(position);
+        self.cells.entry(cell).or_default().push(id.index);
+        self.len += 1;
+        id
+    }
+
+    /// Removes the value with the given `id`, and returns it with its position.
+    ///
+    /// Returns `None` if the `id` was already removed.
+    pub fn remove(&mut self, id: SpatialHashId) -> Option<(Position32, T)> {
+        let entry = self.entries.get_mut(id.index as usize)?;
+        if entry.generation != id.generation {
+            return None;
+        }
+        let (position, value) = entry.item.take()?;
+        entry.generation = entry.generation.wrapping_add(1);
+        self.vacant.push(id.index);
+        self.unlink(id.index, self.cell_of(position));
+        self.len -= 1;
+        Some((position, value))
+    }
+
+    /// Moves the value with the given `id` to a new `position`.
+    ///
+    /// Returns `false` if the `id` was already removed.
+    pub fn move_to(&mut self, id: SpatialHashId, position: Position32) -> bool {
+        let old = match self.get(id) {
+            Some((old, _)) => old,
+            None => return false,
+        };
+        let (from, to) = (self.cell_of(old), self.cell_of(position));
+        if from != to {
+            self.unlink(id.index, from);
+            self.cells.entry(to).or_default().push(id.index);
+        }
+        if let Some((p, _)) = &mut self.entries[id.index as usize].item {
+            *p = position;
+        }
+        true
+    }
+
+    /// Returns the position and value with the given `id`, if it's still stored.
+    pub fn get(&self, id: SpatialHashId) -> Option<(Position32, &T)> {
+        self.entries
+            .get(id.index as usize)
+            .filter(|e| e.generation == id.generation)
+            .and_then(|e| e.item.as_ref())
+            .map(|(position, value)| (*position, value))
+    }
+
+    /// Returns the position and a mutable value with the given `id`,
+    /// if it's still stored.
+    pub fn get_mut(&mut self, id: SpatialHashId) -> Option<(Position32, &mut T)> {
+        self.entries
+            .get_mut(id.index as usize)
+            .filter(|e| e.generation == id.generation)
+            .and_then(|e| e.item.as_mut())
+            .map(|(position, value)| (*position, value))
+    }
+
+    /// Returns an iterator over the values within the given `radius`
+    /// of the `center`, measured with the given `metric`.
+    pub fn query_radius(
+        &self,
+        center: Position32,
+        radius: u32,
+        metric: Metric,
+    ) -> impl Iterator<Item = (SpatialHashId, Position32, &T)> + '_ {
+        let r = radius as i64;
+        let (x, y) = (center.x() as i64, center.y() as i64);
+        self.query_cells((x - r, y - r), (x + r, y + r))
+            .filter(move |(_, p, _)| metric.distance(center, *p) <= radius as u64)
+    }
+
+    /// Returns an iterator over the values inside the given `zone`.
+    pub fn query_zone(
+        &self,
+        zone: Zone32,
+    ) -> impl Iterator<Item = (SpatialHashId, Position32, &T)> + '_ {
+        let (x, y) = (zone.x() as i64, zone.y() as i64);
+        self.query_cells((x, y), (zone.x_end() as i64 - 1, zone.y_end() as i64 - 1))
+            .filter(move |(_, p, _)| zone.contains_position(*p))
+    }
+
+    /// Returns an iterator over the values in the cell of the given `position`,
+    /// and in its eight neighboring cells.
+    pub fn neighbors(
+        &self,
+        position: Position32,
+    ) -> impl Iterator<Item = (SpatialHashId, Position32, &T)> + '_ {
+        let (cx, cy) = self.cell_of(position);
+        self.cells_in((cx - 1, cy - 1), (cx + 1, cy + 1))
+    }
+
+    /// Returns an iterator over all the values.
+    pub fn iter(&self) -> impl Iterator<Item = (SpatialHashId, Position32, &T)> + '_ {
+        self.entries.iter().enumerate().filter_map(|(i, e)| {
+            let id = SpatialHashId {
+                index: i as u32,
+                generation: e.generation,
+            };
+            e.item
+                .as_ref()
+                .map(|(position, value)| (id, *position, value))
+        })
+    }
+
+    /* private helpers */
+
+    fn cell_of(&self, position: Position32) -> (i32, i32) {
+        (
+            position.x().div_euclid(self.cell.w()),
+            position.y().div_euclid(self.cell.h()),
+        )
+    }
+
+    /// Returns the values in the cells covering the given inclusive corners.
+    fn query_cells(
+        &self,
+        min: (i64, i64),
+        max: (i64, i64),
+    ) -> impl Iterator<Item = (SpatialHashId, Position32, &T)> + '_ {
+        let (w, h) = (self.cell.w() as i64, self.cell.h() as i64);
+        let clamp = |c: i64| c.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+        let cell = |(x, y): (i64, i64)| (clamp(x.div_euclid(w)), clamp(y.div_euclid(h)));
+        self.cells_in(cell(min), cell(max))
+    }
+
+    /// Returns the values in the cells between the given inclusive cells.
+    fn cells_in(
+        &self,
+        min: (i32, i32),
+        max: (i32, i32),
+    ) -> impl Iterator<Item = (SpatialHashId, Position32, &T)> + '_ {
+        self.cells
+            .range(min..=max)
+            .filter(move |((_, cy), _)| (min.1..=max.1).contains(cy))
+            .flat_map(|(_, entries)| entries.iter())
+            .map(move |&e| {
+                let entry = &self.entries[e as usize];
+                let (position, value) = entry.item.as_ref().expect("a stored entry");
+                let id = SpatialHashId {
+                    index: e,
+                    generation: entry.generation,
+                };
+                (id, *position, value)
+            })
+    }
+
+    /// Removes an entry from its cell, and the cell if it's left empty.
+    fn unlink(&mut self, index: u32, cell: (i32, i32)) {
+        if let Some(entries) = self.cells.get_mut(&cell) {
+            if let Some(i) = entries.iter().position(|e| *e == index) {
+                entries.swap_remove(i);
+            }
+            if entries.is_empty() {
+                self.cells.remove(&cell);
+            }
+        }
+    }
+}
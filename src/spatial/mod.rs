@@ -0,0 +1,12 @@
+// cuadra::spatial
+//
+//! Spatial indices.
+//
+
+mod hash;
+mod quadtree;
+mod rtree;
+
+pub use hash::{Metric, SpatialHash, SpatialHashId};
+pub use quadtree::{QuadTree32, QuadTreeId};
+pub use rtree::{RTree32, RTree64, RTreeQuery32, RTreeQuery64};
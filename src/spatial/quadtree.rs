@@ -0,0 +1,365 @@
+// cuadra::spatial::quadtree
+//
+//! Quadtree spatial index.
+//
+
+use crate::{Clamper32, Position32, Zone32};
+use alloc::vec::Vec;
+
+/// The identifier of a value in a [`QuadTree32`].
+///
+/// It stays unique after removal, so a stale identifier can't
+/// remove a later value that happens to reuse its slot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QuadTreeId {
+    index: u32,
+    generation: u32,
+}
+
+/// A region of the quadtree, with exclusive ends in a wider primitive.
+#[derive(Clone, Copy, Debug)]
+struct Bounds {
+    x0: i64,
+    y0: i64,
+    x1: i64,
+    y1: i64,
+}
+
+impl Bounds {
+    /// The bounds covering every position of every possible zone.
+    const ROOT: Bounds = Bounds {
+        x0: Clamper32::MIN as i64,
+        y0: Clamper32::MIN as i64,
+        x1: Clamper32::MAX as i64 * 2,
+        y1: Clamper32::MAX as i64 * 2,
+    };
+
+    fn quadrant(&self, q: usize) -> Bounds {
+        let (mx, my) = (
+            self.x0 + (self.x1 - self.x0) / 2,
+            self.y0 + (self.y1 - self.y0) / 2,
+        );
+        let (x0, x1) = if q & 1 == 0 {
+            (self.x0, mx)
+        } else {
+            (mx, self.x1)
+        };
+        let (y0, y1) = if q & 2 == 0 {
+            (self.y0, my)
+        } else {
+            (my, self.y1)
+        };
+        Bounds { x0, y0, x1, y1 }
+    }
+    fn contains(&self, z: &Zone32) -> bool {
+        z.x() as i64 >= self.x0
+            && (z.x_end() as i64) <= self.x1
+            && z.y() as i64 >= self.y0
+            && (z.y_end() as i64) <= self.y1
+    }
+    fn overlaps(&self, z: &Zone32) -> bool {
+        (z.x() as i64) < self.x1
+            && self.x0 < z.x_end() as i64
+            && (z.y() as i64) < self.y1
+            && self.y0 < z.y_end() as i64
+    }
+    fn contains_position(&self, p: Position32) -> bool {
+        (self.x0..self.x1).contains(&(p.x() as i64)) && (self.y0..self.y1).contains(&(p.y() as i64))
+    }
+    /// Returns the squared distance from the position to the closest point inside.
+    fn distance2(&self, p: Position32) -> u128 {
+        distance2(p, self.x0, self.y0, self.x1, self.y1)
+    }
+}
+
+/// Returns the squared distance from a position to a region with exclusive ends.
+fn distance2(p: Position32, x0: i64, y0: i64, x1: i64, y1: i64) -> u128 {
+    let (px, py) = (p.x() as i64, p.y() as i64);
+    let dx = (x0 - px).max(px - (x1 - 1)).max(0) as u128;
+    let dy = (y0 - py).max(py - (y1 - 1)).max(0) as u128;
+    dx * dx + dy * dy
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    bounds: Bounds,
+    depth: u8,
+    /// The index of the first of its four consecutive children, if any.
+    children: Option<u32>,
+    /// The entries stored at this node.
+    entries: Vec<u32>,
+}
+
+#[derive(Clone, Debug)]
+struct Entry<T> {
+    generation: u32,
+    /// The zone, the value and the node where it's stored.
+    item: Option<(Zone32, T, u32)>,
+}
+
+/// A quadtree storing values with a [`Zone32`] bounding box.
+///
+/// The root covers the whole range of representable zones, and each node is
+/// split into four quadrants once it stores more than its capacity of values,
+/// unless it's at the maximum depth. Each value is stored in the deepest node
+/// that fully contains its zone.
+///
+/// Nodes are not merged back when values are removed.
+#[derive(Clone, Debug)]
+pub struct QuadTree32<T> {
+    nodes: Vec<Node>,
+    entries: Vec<Entry<T>>,
+    vacant: Vec<u32>,
+    len: usize,
+    max_depth: u8,
+    capacity: usize,
+}
+
+impl<T> Default for QuadTree32<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> QuadTree32<T> {
+    /// Returns a new empty quadtree, with a maximum depth of `16`,
+    /// and a node capacity of `8`.
+    pub fn new() -> Self {
+        Self::with_config(16, 8)
+    }
+
+    /// Returns a new empty quadtree, with the given maximum depth,
+    /// and node capacity.
+    pub fn with_config(max_depth: u8, capacity: usize) -> Self {
+        Self {
+            nodes: Vec::from([Node {
+                bounds: Bounds::ROOT,
+                depth: 0,
+                children: None,
+                entries: Vec::new(),
+            }]),
+            entries: Vec::new(),
+            vacant: Vec::new(),
+            len: 0,
+            max_depth,
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Returns `true` if there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts a `value` with the given `zone`, and returns its identifier.
+    pub fn insert(&mut self, zone: Zone32, value: T) -> QuadTreeId {
+        let node = self.descend(0, &zone);
+        let id = match self.vacant.pop() {
+            Some(index) => {
+                let entry = &mut self.entries[index as usize];
+                entry.item = Some((zone, value, node));
+                QuadTreeId {
+                    index,
+                    generation: entry.generation,
+                }
+            }
+            None => {
+                self.entries.push(Entry {
+                    generation: 0,
+                    item: Some((zone, value, node)),
+                });
+                QuadTreeId {
+                    index: self.entries.len() as u32 - 1,
+                    generation: 0,
+                }
+            }
+        };
+        self.nodes[node as usize].entries.push(id.index);
+        self.len += 1;
+        self.split(node);
+        id
+    }
+
+    /// Removes the value with the given `id`, and returns it with its zone.
+    ///
+    /// Returns `None` if the `id` was already removed.
+    pub fn remove(&mut self, id: QuadTreeId) -> Option<(Zone32, T)> {
+        let entry = self.entries.get_mut(id.index as usize)?;
+        if entry.generation != id.generation {
+            return None;
+        }
+        let (zone, value, node) = entry.item.take()?;
+        entry.generation = entry.generation.wrapping_add(1);
+        self.vacant.push(id.index);
+        let entries = &mut self.nodes[node as usize].entries;
+        if let Some(i) = entries.iter().position(|e| *e == id.index) {
+            entries.swap_remove(i);
+        }
+        self.len -= 1;
+        Some((zone, value))
+    }
+
+    /// Returns the zone and value with the given `id`, if it's still stored.
+    pub fn get(&self, id: QuadTreeId) -> Option<(Zone32, &T)> {
+        self.entries
+            .get(id.index as usize)
+            .filter(|e| e.generation == id.generation)
+            .and_then(|e| e.item.as_ref())
+            .map(|(zone, value, _)| (*zone, value))
+    }
+
+    /// Returns an iterator over all the values.
+    pub fn iter(&self) -> impl Iterator<Item = (QuadTreeId, Zone32, &T)> + '_ {
+        self.entries.iter().enumerate().filter_map(|(i, e)| {
+            let id = QuadTreeId {
+                index: i as u32,
+                generation: e.generation,
+            };
+            e.item.as_ref().map(|(zone, value, _)| (id, *zone, value))
+        })
+    }
+
+    /// Returns an iterator over the values whose zone overlaps the given `zone`.
+    pub fn query(&self, zone: Zone32) -> impl Iterator<Item = (QuadTreeId, Zone32, &T)> + '_ {
+        self.walk(move |b| b.overlaps(&zone), move |z| z.overlaps(zone))
+    }
+
+    /// Returns an iterator over the values whose zone contains the given `position`.
+    pub fn query_position(
+        &self,
+        position: Position32,
+    ) -> impl Iterator<Item = (QuadTreeId, Zone32, &T)> + '_ {
+        self.walk(
+            move |b| b.contains_position(position),
+            move |z| z.contains_position(position),
+        )
+    }
+
+    /// Returns the value whose zone is the closest to the given `position`.
+    ///
+    /// Zones containing the position have a distance of `0`.
+    /// Ties are resolved in favor of any of them.
+    pub fn nearest(&self, position: Position32) -> Option<(QuadTreeId, Zone32, &T)> {
+        let mut best: Option<(u128, u32)> = None;
+        let mut stack = Vec::from([0_u32]);
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n as usize];
+            if best.map_or(false, |(d, _)| node.bounds.distance2(position) >= d) {
+                continue;
+            }
+            for &e in &node.entries {
+                let (z, _, _) = self.item(e);
+                let d = distance2(
+                    position,
+                    z.x() as i64,
+                    z.y() as i64,
+                    z.x_end() as i64,
+                    z.y_end() as i64,
+                );
+                if best.map_or(true, |(b, _)| d < b) {
+                    best = Some((d, e));
+                }
+            }
+            if let Some(first) = node.children {
+                // visit the closest quadrants first
+                let mut children = [first, first + 1, first + 2, first + 3];
+                children.sort_by_key(|c| {
+                    core::cmp::Reverse(self.nodes[*c as usize].bounds.distance2(position))
+                });
+                stack.extend(children);
+            }
+        }
+        best.map(|(_, e)| self.full_item(e))
+    }
+
+    /* private helpers */
+
+    /// Returns the deepest existing node, from `node`, that fully contains the zone.
+    fn descend(&self, mut node: u32, zone: &Zone32) -> u32 {
+        while let Some(first) = self.nodes[node as usize].children {
+            match (first..first + 4).find(|c| self.nodes[*c as usize].bounds.contains(zone)) {
+                Some(child) => node = child,
+                None => break,
+            }
+        }
+        node
+    }
+
+    /// Splits the node if it's over capacity, and moves down its entries.
+    fn split(&mut self, node: u32) {
+        let n = &self.nodes[node as usize];
+        if n.children.is_some() || n.entries.len() <= self.capacity || n.depth >= self.max_depth {
+            return;
+        }
+        let (bounds, depth) = (n.bounds, n.depth + 1);
+        let first = self.nodes.len() as u32;
+        for q in 0..4 {
+            self.nodes.push(Node {
+                bounds: bounds.quadrant(q),
+                depth,
+                children: None,
+                entries: Vec::new(),
+            });
+        }
+        self.nodes[node as usize].children = Some(first);
+
+        let entries = core::mem::take(&mut self.nodes[node as usize].entries);
+        for e in entries {
+            let zone = self.item(e).0;
+            let target = self.descend(node, &zone);
+            self.nodes[target as usize].entries.push(e);
+            if let Some((_, _, n)) = &mut self.entries[e as usize].item {
+                *n = target;
+            }
+        }
+        for child in first..first + 4 {
+            self.split(child);
+        }
+    }
+
+    fn item(&self, e: u32) -> &(Zone32, T, u32) {
+        self.entries[e as usize]
+            .item
+            .as_ref()
+            .expect("a stored entry")
+    }
+
+    fn full_item(&self, e: u32) -> (QuadTreeId, Zone32, &T) {
+        let entry = &self.entries[e as usize];
+        let (zone, value, _) = self.item(e);
+        let id = QuadTreeId {
+            index: e,
+            generation: entry.generation,
+        };
+        (id, *zone, value)
+    }
+
+    /// Returns an iterator over the entries in the nodes accepted by `visit`,
+    /// whose zone is accepted by `accept`.
+    fn walk<'a>(
+        &'a self,
+        visit: impl Fn(&Bounds) -> bool + 'a,
+        accept: impl Fn(&Zone32) -> bool + 'a,
+    ) -> impl Iterator<Item = (QuadTreeId, Zone32, &'a T)> + 'a {
+        let mut stack = Vec::from([0_u32]);
+        let mut current: &[u32] = &[];
+        core::iter::from_fn(move || loop {
+            if let Some((&e, rest)) = current.split_first() {
+                current = rest;
+                if accept(&self.item(e).0) {
+                    return Some(self.full_item(e));
+                }
+                continue;
+            }
+            let node = &self.nodes[stack.pop()? as usize];
+            if let Some(first) = node.children {
+                stack.extend((first..first + 4).filter(|c| visit(&self.nodes[*c as usize].bounds)));
+            }
+            current = &node.entries;
+        })
+    }
+}
@@ -0,0 +1,294 @@
+// cuadra::spatial::rtree
+//
+//! Bulk-loaded R-trees.
+//
+
+use alloc::{collections::BinaryHeap, vec::Vec};
+use core::cmp::Reverse;
+
+/// The maximum number of children of each node.
+const NODE_CAPACITY: usize = 16;
+
+/// The maximum height of a tree, enough for `NODE_CAPACITY.pow(16)` values.
+const MAX_HEIGHT: usize = 16;
+
+/// Returns the smallest number whose square is at least `n`.
+fn sqrt_ceil(n: usize) -> usize {
+    let mut s = 1;
+    while s * s < n {
+        s += 1;
+    }
+    s
+}
+
+macro_rules! rtree {
+    // $i: inner primitive
+    // $b: bit size
+    // $w: wider primitive
+    ( $($i:ty, $b:expr, $w:ty),+ ) => {
+        $( rtree![single: $i, $b, $w]; )+
+    };
+
+    (single: $i:ty, $b:literal, $w:ty) => { paste::paste! {
+        use crate::{[<Position$b>], [<Zone$b>]};
+
+        /// The bounds of a node, as `[x, y, x_end, y_end]`.
+        type [<Bounds$b>] = [$i; 4];
+
+        #[derive(Clone, Debug)]
+        struct [<RTreeNode$b>] {
+            bounds: [<Bounds$b>],
+            /// The index of the first child, a value for leaves or a node otherwise.
+            first: usize,
+            len: usize,
+        }
+
+        #[doc = "A static R-tree storing values with a [`Zone" $b "`] bounding box."]
+        ///
+        /// It's bulk-loaded once using Sort-Tile-Recursive packing, which
+        /// produces nearly full nodes with little overlap, and is best suited
+        /// for large read-heavy sets of values.
+        ///
+        /// The intersection and containment iterators don't allocate.
+        #[derive(Clone, Debug)]
+        pub struct [<RTree$b>]<T> {
+            /// The values, in leaf order.
+            values: Vec<([<Zone$b>], T)>,
+            /// The nodes, from the leaves up to the root, which is the last one.
+            nodes: Vec<[<RTreeNode$b>]>,
+            /// The number of leaf nodes.
+            leaves: usize,
+        }
+
+        impl<T> Default for [<RTree$b>]<T> {
+            fn default() -> Self {
+                Self {
+                    values: Vec::new(),
+                    nodes: Vec::new(),
+                    leaves: 0,
+                }
+            }
+        }
+
+        impl<T> FromIterator<([<Zone$b>], T)> for [<RTree$b>]<T> {
+            fn from_iter<I: IntoIterator<Item = ([<Zone$b>], T)>>(iter: I) -> Self {
+                Self::bulk_load(iter)
+            }
+        }
+
+        impl<T> [<RTree$b>]<T> {
+            /// Returns a new tree containing the given values with their zones.
+            pub fn bulk_load(values: impl IntoIterator<Item = ([<Zone$b>], T)>) -> Self {
+                let mut values: Vec<_> = values.into_iter().collect();
+                [<str_sort$b>](&mut values, |(zone, _)| [<bounds$b>](zone));
+
+                let mut nodes: Vec<[<RTreeNode$b>]> = values
+                    .chunks(NODE_CAPACITY)
+                    .enumerate()
+                    .map(|(c, chunk)| [<RTreeNode$b>] {
+                        bounds: [<merge$b>](chunk.iter().map(|(zone, _)| [<bounds$b>](zone))),
+                        first: c * NODE_CAPACITY,
+                        len: chunk.len(),
+                    })
+                    .collect();
+                let leaves = nodes.len();
+
+                let mut level = 0..nodes.len();
+                while level.len() > 1 {
+                    [<str_sort$b>](&mut nodes[level.clone()], |node| node.bounds);
+                    let start = nodes.len();
+                    for first in level.clone().step_by(NODE_CAPACITY) {
+                        let end = (first + NODE_CAPACITY).min(level.end);
+                        let bounds = [<merge$b>](nodes[first..end].iter().map(|node| node.bounds));
+                        nodes.push([<RTreeNode$b>] {
+                            bounds,
+                            first,
+                            len: end - first,
+                        });
+                    }
+                    level = start..nodes.len();
+                }
+                Self { values, nodes, leaves }
+            }
+
+            /// Returns the number of values.
+            pub fn len(&self) -> usize {
+                self.values.len()
+            }
+            /// Returns `true` if there are no values.
+            pub fn is_empty(&self) -> bool {
+                self.values.is_empty()
+            }
+
+            /// Returns an iterator over all the values, in no particular order.
+            pub fn iter(&self) -> impl Iterator<Item = ([<Zone$b>], &T)> + '_ {
+                self.values.iter().map(|(zone, value)| (*zone, value))
+            }
+
+            /// Returns an iterator over the values whose zone overlaps the given `zone`.
+            pub fn intersecting(&self, zone: [<Zone$b>]) -> [<RTreeQuery$b>]<'_, T> {
+                [<RTreeQuery$b>]::new(self, [<Query$b>]::Intersecting(zone))
+            }
+            /// Returns an iterator over the values whose zone is fully contained
+            /// in the given `zone`.
+            pub fn contained_in(&self, zone: [<Zone$b>]) -> [<RTreeQuery$b>]<'_, T> {
+                [<RTreeQuery$b>]::new(self, [<Query$b>]::ContainedIn(zone))
+            }
+            /// Returns an iterator over the values whose zone contains the given `position`.
+            pub fn containing(&self, position: [<Position$b>]) -> [<RTreeQuery$b>]<'_, T> {
+                [<RTreeQuery$b>]::new(self, [<Query$b>]::Containing(position))
+            }
+
+            /// Returns up to `k` values whose zones are the closest to the
+            /// given `position`, from the closest to the farthest.
+            ///
+            /// Zones containing the position have a distance of `0`.
+            pub fn nearest_k(&self, position: [<Position$b>], k: usize) -> Vec<([<Zone$b>], &T)> {
+                let mut nearest = Vec::with_capacity(k.min(self.len()));
+                if k == 0 || self.nodes.is_empty() {
+                    return nearest;
+                }
+                // (distance, is a node, index), with values popped before nodes on ties
+                let mut heap = BinaryHeap::from([Reverse((0_u128, true, self.nodes.len() - 1))]);
+                while let Some(Reverse((_, is_node, index))) = heap.pop() {
+                    if !is_node {
+                        let (zone, value) = &self.values[index];
+                        nearest.push((*zone, value));
+                        if nearest.len() == k {
+                            break;
+                        }
+                        continue;
+                    }
+                    let node = &self.nodes[index];
+                    for child in node.first..node.first + node.len {
+                        let (bounds, is_node) = if index < self.leaves {
+                            ([<bounds$b>](&self.values[child].0), false)
+                        } else {
+                            (self.nodes[child].bounds, true)
+                        };
+                        heap.push(Reverse(([<distance2_$b>](position, bounds), is_node, child)));
+                    }
+                }
+                nearest
+            }
+        }
+
+        #[derive(Clone, Copy, Debug)]
+        enum [<Query$b>] {
+            Intersecting([<Zone$b>]),
+            ContainedIn([<Zone$b>]),
+            Containing([<Position$b>]),
+        }
+
+        impl [<Query$b>] {
+            /// Whether the children of a node with these bounds can match.
+            fn visits(&self, b: &[<Bounds$b>]) -> bool {
+                match self {
+                    Self::Intersecting(z) | Self::ContainedIn(z) => {
+                        z.x() < b[2] && b[0] < z.x_end() && z.y() < b[3] && b[1] < z.y_end()
+                    }
+                    Self::Containing(p) => (b[0]..b[2]).contains(&p.x()) && (b[1]..b[3]).contains(&p.y()),
+                }
+            }
+            /// Whether a value with this zone matches.
+            fn accepts(&self, zone: [<Zone$b>]) -> bool {
+                match self {
+                    Self::Intersecting(z) => z.overlaps(zone),
+                    Self::ContainedIn(z) => z.contains_zone(zone),
+                    Self::Containing(p) => zone.contains_position(*p),
+                }
+            }
+        }
+
+        #[doc = "An iterator over the values of an [`RTree" $b "`] matching a query."]
+        #[derive(Clone, Debug)]
+        pub struct [<RTreeQuery$b>]<'a, T> {
+            tree: &'a [<RTree$b>]<T>,
+            query: [<Query$b>],
+            /// The nodes being visited, with the offset of their next child.
+            stack: [(usize, usize); MAX_HEIGHT],
+            depth: usize,
+        }
+
+        impl<'a, T> [<RTreeQuery$b>]<'a, T> {
+            fn new(tree: &'a [<RTree$b>]<T>, query: [<Query$b>]) -> Self {
+                let mut iter = Self {
+                    tree,
+                    query,
+                    stack: [(0, 0); MAX_HEIGHT],
+                    depth: 0,
+                };
+                if let Some(root) = tree.nodes.last() {
+                    if query.visits(&root.bounds) {
+                        iter.stack[0] = (tree.nodes.len() - 1, 0);
+                        iter.depth = 1;
+                    }
+                }
+                iter
+            }
+        }
+
+        impl<'a, T> Iterator for [<RTreeQuery$b>]<'a, T> {
+            type Item = ([<Zone$b>], &'a T);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                while self.depth > 0 {
+                    let (index, next) = &mut self.stack[self.depth - 1];
+                    let node = &self.tree.nodes[*index];
+                    if *next == node.len {
+                        self.depth -= 1;
+                        continue;
+                    }
+                    let child = node.first + *next;
+                    *next += 1;
+                    if *index < self.tree.leaves {
+                        let (zone, value) = &self.tree.values[child];
+                        if self.query.accepts(*zone) {
+                            return Some((*zone, value));
+                        }
+                    } else if self.query.visits(&self.tree.nodes[child].bounds) {
+                        self.stack[self.depth] = (child, 0);
+                        self.depth += 1;
+                    }
+                }
+                None
+            }
+        }
+
+        fn [<bounds$b>](zone: &[<Zone$b>]) -> [<Bounds$b>] {
+            [zone.x(), zone.y(), zone.x_end(), zone.y_end()]
+        }
+
+        /// Returns the bounds covering all the given bounds.
+        fn [<merge$b>](mut bounds: impl Iterator<Item = [<Bounds$b>]>) -> [<Bounds$b>] {
+            let first = bounds.next().unwrap_or_default();
+            bounds.fold(first, |a, b| [a[0].min(b[0]), a[1].min(b[1]), a[2].max(b[2]), a[3].max(b[3])])
+        }
+
+        /// Returns the squared distance from the position to the closest point of the bounds,
+        /// saturating at the extremes of the widest range.
+        fn [<distance2_$b>](p: [<Position$b>], b: [<Bounds$b>]) -> u128 {
+            let (px, py) = (p.x() as $w, p.y() as $w);
+            let dx = (b[0] as $w - px).max(px - (b[2] as $w - 1)).max(0) as u128;
+            let dy = (b[1] as $w - py).max(py - (b[3] as $w - 1)).max(0) as u128;
+            (dx * dx).saturating_add(dy * dy)
+        }
+
+        /// Sorts the elements in Sort-Tile-Recursive order: in vertical slices
+        /// by the center `x`, and each slice by the center `y`.
+        fn [<str_sort$b>]<E>(elements: &mut [E], bounds: impl Fn(&E) -> [<Bounds$b>]) {
+            let nodes = (elements.len() + NODE_CAPACITY - 1) / NODE_CAPACITY;
+            let slice = sqrt_ceil(nodes) * NODE_CAPACITY;
+            // the doubled center, to stay exact
+            let center = |e: &E, axis: usize| {
+                let b = bounds(e);
+                b[axis] as $w + b[axis + 2] as $w
+            };
+            elements.sort_by_key(|e| center(e, 0));
+            for chunk in elements.chunks_mut(slice) {
+                chunk.sort_by_key(|e| center(e, 1));
+            }
+        }
+    }};
+}
+rtree![i32, 32, i64, i64, 64, i128];
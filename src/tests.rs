@@ -103,3 +103,1206 @@ fn clamping_32() {
     assert_eq![C::MAX as usize, C::clamp_positive_to_usize(i32::MAX)];
     assert_eq![1_usize, C::clamp_positive_to_usize(i32::MIN)];
 }
+
+#[test]
+fn zone_geometry() {
+    let a = Zone16::new_raw(0, 0, 10, 10);
+    let b = Zone16::new_raw(5, 5, 10, 10);
+    let c = Zone16::new_raw(10, 0, 5, 5); // touches `a` at its right edge
+
+    assert_eq![15, b.x_end()];
+    assert![a.contains_position(Position16::new(9, 9))];
+    assert![!a.contains_position(Position16::new(10, 9))];
+    assert![a.contains_zone(Zone16::new_raw(2, 2, 8, 8))];
+    assert![!a.contains_zone(b)];
+
+    assert![a.overlaps(b)];
+    assert![!a.overlaps(c)];
+    assert_eq![Some(Zone16::new_raw(5, 5, 5, 5)), a.intersection(b)];
+    assert_eq![None, a.intersection(c)];
+    assert_eq![Zone16::new_raw(0, 0, 15, 15), a.union(b)];
+    assert_eq![Zone16::new_raw(0, 0, 15, 10), a.union(c)];
+
+    // the bounding box of far apart zones gets its size clamped
+    let far = Zone8::new_raw(-64, -64, 1, 1).union(Zone8::new_raw(63, 63, 63, 63));
+    assert_eq![Zone8::new_raw(-64, -64, 63, 63), far];
+}
+
+#[test]
+fn zone_difference() {
+    let a = Zone16::new_raw(0, 0, 10, 10);
+
+    // a hole in the middle leaves four parts
+    let d = a.difference(Zone16::new_raw(3, 3, 4, 4));
+    assert_eq![
+        &[
+            Zone16::new_raw(0, 0, 10, 3),
+            Zone16::new_raw(0, 3, 3, 4),
+            Zone16::new_raw(7, 3, 3, 4),
+            Zone16::new_raw(0, 7, 10, 3),
+        ],
+        d.as_slice()
+    ];
+
+    // no overlap leaves the zone untouched, full cover leaves nothing
+    assert_eq![&[a], a.difference(Zone16::new_raw(10, 0, 5, 5)).as_slice()];
+    assert![a.difference(Zone16::new_raw(-1, -1, 20, 20)).is_empty()];
+
+    // the parts and the intersection cover the whole zone
+    let b = Zone16::new_raw(6, -2, 10, 5);
+    let area = |z: &Zone16| z.w() as i32 * z.h() as i32;
+    let parts: i32 = a.difference(b).iter().map(area).sum();
+    assert_eq![100, parts + area(&a.intersection(b).unwrap())];
+
+    // the parts starting beyond the clamped maximum are left out
+    let (a, b) = (
+        Zone8::new_raw(23, 47, 46, 35),
+        Zone8::new_raw(58, 52, 7, 11),
+    );
+    let d = a.difference(b);
+    assert_eq![
+        &[
+            Zone8::new_raw(23, 47, 46, 5),
+            Zone8::new_raw(23, 52, 35, 11),
+            Zone8::new_raw(23, 63, 46, 19),
+        ],
+        d.as_slice()
+    ];
+    assert![d.iter().all(|z| !z.overlaps(b))];
+    let b = Zone8::new_raw(20, 60, 10, 10);
+    assert_eq![
+        &[
+            Zone8::new_raw(23, 47, 46, 13),
+            Zone8::new_raw(30, 60, 39, 10),
+        ],
+        a.difference(b).as_slice()
+    ];
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn layout_split() {
+    use Constraint::*;
+    let zone = Zone16::new_raw(10, 20, 100, 5);
+    let lengths = |constraints: &[Constraint]| -> Vec<i16> {
+        let split = zone.split(Axis::Horizontal, constraints);
+        split.iter().map(|z| z.map_or(0, |z| z.w())).collect()
+    };
+
+    let split = zone.split(Axis::Horizontal, &[Length(30), Fill(1)]);
+    assert_eq![Some(Zone16::new_raw(10, 20, 30, 5)), split[0]];
+    assert_eq![Some(Zone16::new_raw(40, 20, 70, 5)), split[1]];
+    let split = zone.split(Axis::Vertical, &[Length(2), Fill(1)]);
+    assert_eq![Some(Zone16::new_raw(10, 22, 100, 3)), split[1]];
+
+    assert_eq![
+        vec![25, 30, 0],
+        lengths(&[Percentage(25), Ratio(3, 10), Max(0)])
+    ];
+    // the remainder goes to the first fill children
+    assert_eq![vec![34, 33, 33], lengths(&[Fill(1), Fill(1), Fill(1)])];
+    assert_eq![vec![25, 75], lengths(&[Fill(1), Fill(3)])];
+    // min grows, max is capped, the rest goes to fill
+    assert_eq![vec![55, 10, 35], lengths(&[Min(20), Max(10), Fill(1)])];
+    // unclaimed leftover is left empty
+    assert_eq![vec![10, 20], lengths(&[Length(10), Max(20)])];
+    // overflowing children get truncated in order
+    assert_eq![vec![80, 20, 0], lengths(&[Length(80), Length(80), Min(5)])];
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn layout_flex() {
+    let container = Zone32::new_raw(0, 0, 100, 10);
+    let item = |w, h| FlexItem::new(Size32::new(w, h));
+
+    // grow by weight, stretch the cross axis
+    let zones = Flex::new(Axis::Horizontal).gap(10).layout(
+        container,
+        &[item(10, 2), item(10, 2).grow(1), item(10, 2).grow(3)],
+    );
+    assert_eq![
+        vec![
+            Zone32::new_raw(0, 0, 10, 10),
+            Zone32::new_raw(20, 0, 23, 10),
+            Zone32::new_raw(53, 0, 47, 10),
+        ],
+        zones
+    ];
+
+    // justify and align
+    let zones = Flex::new(Axis::Horizontal)
+        .justify_content(JustifyContent::SpaceBetween)
+        .align_items(AlignItems::Center)
+        .layout(container, &[item(10, 2), item(10, 4), item(10, 2)]);
+    assert_eq![Zone32::new_raw(45, 3, 10, 4), zones[1]];
+    assert_eq![Zone32::new_raw(90, 4, 10, 2), zones[2]];
+
+    // shrink proportionally to the base length
+    let zones = Flex::new(Axis::Vertical).layout(
+        Zone32::new_raw(5, 5, 3, 30),
+        &[item(1, 20), item(1, 40).shrink(1)],
+    );
+    assert_eq![Zone32::new_raw(5, 5, 3, 10), zones[0]];
+    assert_eq![Zone32::new_raw(5, 15, 3, 20), zones[1]];
+
+    // wrap into lines as tall as their tallest item
+    let zones = Flex::new(Axis::Horizontal)
+        .wrap(true)
+        .gap(1)
+        .align_items(AlignItems::Start)
+        .layout(container, &[item(60, 2), item(30, 3), item(50, 1)]);
+    assert_eq![Zone32::new_raw(61, 0, 30, 3), zones[1]];
+    assert_eq![Zone32::new_raw(0, 4, 50, 1), zones[2]];
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn layout_grid() {
+    use Track::*;
+    let grid = Grid::new(&[Fixed(20), Fr(1), Fr(2)], &[Auto, MinMax(5, 10), Auto])
+        .gap(2, 1)
+        .template_areas(&["head head head", "side main main", "side main main"]);
+    let head = GridItem::new(grid.named_area("head").unwrap()).content(Size32::new(1, 3));
+    let side = GridItem::new(grid.named_area("side").unwrap());
+    let main = GridItem::new(grid.named_area("main").unwrap());
+    let cell = GridItem::new(GridArea::new(2, 2)).content(Size32::new(1, 4));
+    let outside = GridItem::new(GridArea::new(2, 2).span(2, 1));
+
+    let zones = grid.layout(
+        Zone32::new_raw(0, 0, 100, 40),
+        &[head, side, main, cell, outside],
+    );
+    // columns: 20, 26, 50 — rows: 3 + 11 stretched, 10, 4 + 10 stretched
+    assert_eq![Some(Zone32::new_raw(0, 0, 100, 14)), zones[0]];
+    assert_eq![Some(Zone32::new_raw(0, 15, 20, 25)), zones[1]];
+    assert_eq![Some(Zone32::new_raw(22, 15, 78, 25)), zones[2]];
+    assert_eq![Some(Zone32::new_raw(50, 26, 50, 14)), zones[3]];
+    assert_eq![None, zones[4]];
+}
+
+#[test]
+fn insets() {
+    let zone = Zone16::new_raw(10, 10, 20, 10);
+    let border = Insets16::uniform(1);
+    let padding = Insets16::symmetric(2, 3);
+
+    assert_eq![Insets16::new(3, 4, 3, 4), border + padding];
+    assert_eq![Insets16::new(0, 1, 0, 1), padding - border - border];
+    assert_eq![Insets16::new(0, 0, 0, 0), Insets16::uniform(-5)];
+
+    assert_eq![Zone16::new_raw(14, 13, 12, 4), zone.inset(border + padding)];
+    assert_eq![zone, zone.inset(padding).outset(padding)];
+    // the size stays at least 1, inside the original zone
+    assert_eq![
+        Zone16::new_raw(20, 19, 1, 1),
+        zone.inset(Insets16::uniform(10))
+    ];
+    assert_eq![
+        Zone16::new_raw(29, 12, 1, 6),
+        zone.inset(Insets16::new(2, 0, 2, 50))
+    ];
+    assert_eq![
+        Zone16::new_raw(10, 10, 1, 10),
+        zone.inset(Insets16::new(0, 50, 0, 0))
+    ];
+
+    assert_eq![Size16::new(26, 14), zone.size().with_insets(padding)];
+}
+
+#[test]
+fn align() {
+    let zone = Zone16::new_raw(10, 20, 10, 5);
+    let size = Size16::new(4, 2);
+
+    assert_eq![
+        Zone16::new_raw(13, 21, 4, 2),
+        zone.place(size, Align::Center, Align::Center)
+    ];
+    assert_eq![
+        Zone16::new_raw(16, 20, 4, 5),
+        zone.place(size, Align::End, Align::Stretch)
+    ];
+    assert_eq![
+        Zone16::new_raw(10, 23, 4, 2),
+        zone.place(size, Align::Start, Align::End)
+    ];
+    // a bigger child overflows at both ends when centered
+    let big = Size16::new(14, 5);
+    assert_eq![
+        Zone16::new_raw(8, 20, 14, 5),
+        zone.place(big, Align::Center, Align::Start)
+    ];
+
+    assert_eq![Position16::new(10, 20), zone.anchor(Anchor::TopLeft)];
+    assert_eq![Position16::new(14, 22), zone.anchor(Anchor::Center)];
+    assert_eq![Position16::new(19, 24), zone.anchor(Anchor::BottomRight)];
+    assert_eq![(Align::End, Align::Center), Anchor::Right.aligns()];
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn pack() {
+    use PackAlgorithm::*;
+    let bin = Size16::new(64, 64);
+    let bounds = Zone16::new(Position16::new(0, 0), bin);
+    let mut items = vec![Size16::new(64, 16), Size16::new(48, 16)];
+    items.extend([Size16::new(16, 16); 9]);
+    items.push(Size16::new(65, 1));
+
+    for algorithm in [Shelf, Skyline, Guillotine, MaxRects] {
+        let packing = Packer::new(algorithm).pack(bin, &items);
+        assert_eq![vec![11], packing.unplaced().collect::<Vec<_>>()];
+        let placed: Vec<Zone16> = packing.placements().iter().flatten().copied().collect();
+        for (i, a) in placed.iter().enumerate() {
+            assert![bounds.contains_zone(*a)];
+            assert![placed[i + 1..].iter().all(|b| !a.overlaps(*b))];
+        }
+    }
+
+    // the shelves can only fit a tall item by rotating it
+    items[1] = Size16::new(16, 48);
+    let packing = Packer::new(Shelf).pack(bin, &items);
+    assert_eq![
+        vec![5, 6, 7, 8, 9, 10, 11],
+        packing.unplaced().collect::<Vec<_>>()
+    ];
+    let packing = Packer::new(Shelf).rotation(true).pack(bin, &items);
+    assert_eq![
+        Some(Zone16::new_raw(0, 16, 48, 16)),
+        packing.placements()[1]
+    ];
+    assert_eq![vec![11], packing.unplaced().collect::<Vec<_>>()];
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn atlas() {
+    let mut atlas = AtlasAllocator::new(Size32::new(64, 64));
+    let (a, za) = atlas.allocate(Size32::new(32, 32)).unwrap();
+    let (b, zb) = atlas.allocate(Size32::new(32, 32)).unwrap();
+    let (c, _) = atlas.allocate(Size32::new(32, 64)).unwrap();
+    assert![!za.overlaps(zb)];
+    assert_eq![None, atlas.allocate(Size32::new(1, 1))];
+    assert_eq![0, atlas.stats().free_area];
+
+    // freed neighbors coalesce back into a single rectangle
+    assert_eq![Some(za), atlas.deallocate(a)];
+    assert_eq![None, atlas.deallocate(a)];
+    atlas.deallocate(b);
+    let stats = atlas.stats();
+    assert_eq![1, stats.allocations];
+    assert_eq![32 * 64, stats.free_area];
+    assert_eq![Some(Zone32::new_raw(0, 0, 32, 64)), stats.largest_free];
+
+    // a stale id doesn't affect a reused slot
+    let (d, _) = atlas.allocate(Size32::new(32, 64)).unwrap();
+    assert_eq![None, atlas.get(b)];
+    assert![atlas.get(d).is_some()];
+
+    // growing keeps allocations in place
+    let zc = atlas.get(c).unwrap();
+    atlas.grow(Size32::new(128, 64));
+    assert_eq![Some(zc), atlas.get(c)];
+    assert_eq![
+        Some(Zone32::new_raw(64, 0, 64, 64)),
+        atlas.stats().largest_free
+    ];
+    assert![atlas.allocate(Size32::new(64, 64)).is_some()];
+
+    // the free space doesn't fragment after freeing everything
+    let mut seed = 7_u32;
+    let mut rand = |n: u32| {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        (seed >> 16) % n + 1
+    };
+    for _ in 0..50 {
+        let size = Size32::new(rand(64) as i32, rand(64) as i32);
+        let mut atlas = AtlasAllocator::new(size);
+        let mut ids = Vec::new();
+        while let Some((id, _)) = atlas.allocate(Size32::new(rand(16) as i32, rand(16) as i32)) {
+            ids.push(id);
+            if rand(4) == 1 {
+                atlas.deallocate(ids.swap_remove(0));
+            }
+        }
+        let live: Vec<_> = ids.iter().filter_map(|&id| atlas.get(id)).collect();
+        for (i, a) in live.iter().enumerate() {
+            assert![live[i + 1..].iter().all(|b| !a.overlaps(*b))];
+        }
+        for id in ids {
+            atlas.deallocate(id);
+        }
+        let free = Zone32::new(Position32::new(0, 0), size);
+        assert_eq![Some(free), atlas.stats().largest_free];
+        assert_eq![Some(free), atlas.allocate(size).map(|(_, z)| z)];
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn quadtree() {
+    let mut tree = QuadTree32::with_config(8, 2);
+    let mut ids = Vec::new();
+    for y in 0..10 {
+        for x in 0..10 {
+            ids.push(tree.insert(Zone32::new_raw(x * 10, y * 10, 5, 5), (x, y)));
+        }
+    }
+    // a huge zone that stays at the root
+    let big = tree.insert(Zone32::new_raw(-1_000, -1_000, 2_000, 2_000), (-1, -1));
+    assert_eq![101, tree.len()];
+
+    let mut found: Vec<_> = tree
+        .query(Zone32::new_raw(12, 12, 10, 10))
+        .map(|(_, _, v)| *v)
+        .collect();
+    found.sort();
+    assert_eq![vec![(-1, -1), (1, 1), (1, 2), (2, 1), (2, 2)], found];
+
+    let found: Vec<_> = tree
+        .query_position(Position32::new(34, 54))
+        .map(|(_, _, v)| *v)
+        .collect();
+    assert_eq![2, found.len()];
+    assert![found.contains(&(3, 5))];
+
+    assert_eq![
+        Some((Zone32::new_raw(-1_000, -1_000, 2_000, 2_000), (-1, -1))),
+        tree.remove(big)
+    ];
+    assert_eq![None, tree.remove(big)];
+    assert![tree
+        .query_position(Position32::new(36, 56))
+        .next()
+        .is_none()];
+
+    assert_eq![(7, 3), *tree.nearest(Position32::new(77, 37)).unwrap().2];
+    assert_eq![(9, 0), *tree.nearest(Position32::new(500, -20)).unwrap().2];
+    tree.remove(ids[9]);
+    assert_eq![(9, 1), *tree.nearest(Position32::new(500, -20)).unwrap().2];
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn rtree() {
+    let mut zones = Vec::new();
+    for y in 0..40 {
+        for x in 0..40 {
+            zones.push((
+                Zone64::new_raw(x * 1_000_000_000, y * 1_000_000_000, 10, 10),
+                (x, y),
+            ));
+        }
+    }
+    let tree: RTree64<_> = zones.into_iter().collect();
+    assert_eq![1_600, tree.len()];
+
+    let mut found: Vec<_> = tree
+        .intersecting(Zone64::new_raw(
+            2_000_000_005,
+            3_000_000_005,
+            1_000_000_000,
+            10,
+        ))
+        .map(|(_, v)| *v)
+        .collect();
+    found.sort();
+    assert_eq![vec![(2, 3), (3, 3)], found];
+
+    let found: Vec<_> = tree
+        .contained_in(Zone64::new_raw(
+            2_000_000_005,
+            3_000_000_000,
+            1_000_000_010,
+            10,
+        ))
+        .map(|(_, v)| *v)
+        .collect();
+    assert_eq![vec![(3, 3)], found];
+
+    let found: Vec<_> = tree
+        .containing(Position64::new(39_000_000_009, 0))
+        .map(|(_, v)| *v)
+        .collect();
+    assert_eq![vec![(39, 0)], found];
+    assert_eq![0, tree.containing(Position64::new(-1, 0)).count()];
+
+    let nearest: Vec<_> = tree
+        .nearest_k(Position64::new(5_400_000_000, 5_000_000_000), 3)
+        .into_iter()
+        .map(|(_, v)| *v)
+        .collect();
+    assert_eq![vec![(5, 5), (6, 5), (5, 4)], nearest];
+
+    let empty: RTree32<()> = RTree32::bulk_load([]);
+    assert![empty
+        .intersecting(Zone32::new_raw(0, 0, 10, 10))
+        .next()
+        .is_none()];
+    assert![empty.nearest_k(Position32::new(0, 0), 2).is_empty()];
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn spatial_hash() {
+    let mut hash = SpatialHash::new(Size32::new(10, 10));
+    let a = hash.insert(Position32::new(0, 0), 'a');
+    let b = hash.insert(Position32::new(3, 4), 'b');
+    let c = hash.insert(Position32::new(-5, 5), 'c');
+    let d = hash.insert(Position32::new(25, 0), 'd');
+
+    let sorted = |iter: &mut dyn Iterator<Item = char>| {
+        let mut v: Vec<_> = iter.collect();
+        v.sort();
+        v
+    };
+    let center = Position32::new(0, 0);
+    assert_eq![
+        vec!['a', 'b', 'c'],
+        sorted(
+            &mut hash
+                .query_radius(center, 5, Metric::Chebyshev)
+                .map(|(_, _, v)| *v)
+        )
+    ];
+    assert_eq![
+        vec!['a'],
+        sorted(
+            &mut hash
+                .query_radius(center, 6, Metric::Manhattan)
+                .map(|(_, _, v)| *v)
+        )
+    ];
+    assert_eq![
+        vec!['b', 'd'],
+        sorted(
+            &mut hash
+                .query_zone(Zone32::new_raw(1, 0, 25, 5))
+                .map(|(_, _, v)| *v)
+        )
+    ];
+    assert_eq![
+        vec!['a', 'b', 'c'],
+        sorted(&mut hash.neighbors(Position32::new(9, 9)).map(|(_, _, v)| *v))
+    ];
+
+    assert![hash.move_to(d, Position32::new(-8, -8))];
+    assert_eq![Some((Position32::new(-8, -8), &'d')), hash.get(d)];
+    assert_eq![
+        vec!['a', 'b', 'c', 'd'],
+        sorted(&mut hash.neighbors(Position32::new(0, 0)).map(|(_, _, v)| *v))
+    ];
+    assert_eq![Some((Position32::new(-5, 5), 'c')), hash.remove(c)];
+    assert![!hash.move_to(c, Position32::new(0, 0))];
+    assert_eq![3, hash.len()];
+    assert_eq![2, hash.query_radius(center, 7, Metric::Manhattan).count()];
+    assert_eq![Some('a'), hash.remove(a).map(|(_, v)| v)];
+    assert![hash.get_mut(b).map(|(_, v)| *v = 'B').is_some()];
+    assert_eq![vec!['B', 'd'], sorted(&mut hash.iter().map(|(_, _, v)| *v))];
+
+    // radiuses reaching beyond the cells range
+    let mut hash = SpatialHash::new(Size32::new(1, 1));
+    hash.insert(Position32::new(Clamper32::MIN, Clamper32::MAX), 'e');
+    let far = hash.query_radius(Position32::new(0, 0), 3_000_000_000, Metric::Chebyshev);
+    assert_eq![vec!['e'], far.map(|(_, _, v)| *v).collect::<Vec<_>>()];
+    let far = hash.query_radius(Position32::new(0, 0), u32::MAX, Metric::Manhattan);
+    assert_eq![1, far.count()];
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn damage_tracker() {
+    let mut damage = DamageTracker::new(Size16::new(80, 24));
+    damage.add(Zone16::new_raw(0, 0, 10, 1));
+    damage.add(Zone16::new_raw(10, 0, 5, 1));
+    assert_eq![&[Zone16::new_raw(0, 0, 15, 1)], damage.rects()];
+
+    // overlapping without merging stays disjoint
+    damage.add(Zone16::new_raw(5, 0, 20, 3));
+    damage.add(Zone16::new_raw(-5, -5, 10, 10));
+    assert_eq![85, damage.area()];
+    for (i, a) in damage.rects().iter().enumerate() {
+        assert![damage.rects()[i + 1..].iter().all(|b| !a.overlaps(*b))];
+    }
+    damage.add(Zone16::new_raw(70, 20, 20, 20));
+    assert![damage.rects().contains(&Zone16::new_raw(70, 20, 10, 4))];
+    damage.add_screen();
+    assert_eq![vec![Zone16::new_raw(0, 0, 80, 24)], damage.take()];
+    assert![damage.is_empty()];
+
+    let mut damage = DamageTracker::new(Size16::new(80, 24)).max_waste(10);
+    damage.add(Zone16::new_raw(0, 0, 10, 1));
+    damage.add(Zone16::new_raw(0, 1, 5, 1));
+    assert_eq![vec![Zone16::new_raw(0, 0, 10, 2)], damage.take()];
+
+    let mut damage = DamageTracker::new(Size16::new(80, 24)).max_rects(2);
+    damage.add(Zone16::new_raw(0, 10, 1, 1));
+    damage.add(Zone16::new_raw(0, 0, 1, 1));
+    damage.add(Zone16::new_raw(5, 10, 1, 1));
+    assert_eq![
+        vec![Zone16::new_raw(0, 0, 1, 1), Zone16::new_raw(0, 10, 6, 1)],
+        damage.take()
+    ];
+
+    // merging split pieces must not rebuild the zone they were split from
+    let mut damage = DamageTracker::new(Size16::new(30, 20))
+        .max_rects(2)
+        .max_waste(10);
+    damage.add(Zone16::new_raw(0, 0, 18, 20));
+    damage.add(Zone16::new_raw(29, 8, 1, 10));
+    damage.add(Zone16::new_raw(25, 3, 11, 6));
+    assert_eq![2, damage.rects().len()];
+    assert![!damage.rects()[0].overlaps(damage.rects()[1])];
+    for (x, y, w, h) in [(0, 0, 18, 20), (29, 8, 1, 10), (25, 3, 5, 6)] {
+        for p in (x..x + w).flat_map(|x| (y..y + h).map(move |y| Position16::new(x, y))) {
+            assert![damage.rects().iter().any(|r| r.contains_position(p))];
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn region() {
+    let a = Region32::from_zone(Zone32::new_raw(0, 0, 10, 10));
+    let b = Region32::from_zone(Zone32::new_raw(5, 5, 10, 10));
+
+    let union = a.union(&b);
+    assert_eq![175, union.area()];
+    assert_eq![Some(Zone32::new_raw(0, 0, 15, 15)), union.bounds()];
+    assert_eq![
+        vec![
+            Zone32::new_raw(0, 0, 10, 5),
+            Zone32::new_raw(0, 5, 15, 5),
+            Zone32::new_raw(5, 10, 10, 5)
+        ],
+        union.rects().collect::<Vec<_>>()
+    ];
+    assert![union.contains_position(Position32::new(14, 14))];
+    assert![!union.contains_position(Position32::new(14, 4))];
+
+    let inter = a.intersection(&b);
+    assert_eq![Region32::from_zone(Zone32::new_raw(5, 5, 5, 5)), inter];
+    assert_eq![75, a.difference(&b).area()];
+    assert_eq![150, a.xor(&b).area()];
+    assert_eq![union, a.xor(&b).union(&inter)];
+    assert![a.difference(&a).is_empty()];
+
+    // normalized, regardless of how it's built
+    let halves: Region32 = [Zone32::new_raw(0, 0, 5, 10), Zone32::new_raw(5, 0, 5, 10)]
+        .into_iter()
+        .collect();
+    assert_eq![a, halves];
+    assert_eq![
+        Region32::from_zone(Zone32::new_raw(-3, 7, 10, 10)),
+        a.translate(-3, 7)
+    ];
+
+    // spans wider than an i32
+    let (min, max) = (Clamper32::MIN, Clamper32::MAX);
+    let wide: Region32 = [
+        Zone32::new_raw(min, 0, max, 1),
+        Zone32::new_raw(-1, 0, max, 2),
+        Zone32::new_raw(max - 1, 0, max, 1),
+    ]
+    .into_iter()
+    .collect();
+    let span = (2 * max as i64 - 1 - min as i64) as u64;
+    assert_eq![span + max as u64, wide.area()];
+    assert_eq![Some(Zone32::new_raw(min, 0, max, 2)), wide.bounds()];
+    assert_eq![
+        vec![
+            Zone32::new_raw(min, 0, max, 1),
+            Zone32::new_raw(-1, 1, max, 1)
+        ],
+        wide.rects().collect::<Vec<_>>()
+    ];
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn zone_positions() {
+    let zone = Zone8::new_raw(1, 2, 3, 2);
+    fn xy(iter: impl Iterator<Item = Position8>) -> Vec<(i8, i8)> {
+        iter.map(|p| p.as_tuple()).collect()
+    }
+
+    assert_eq![
+        vec![(1, 2), (2, 2), (3, 2), (1, 3), (2, 3), (3, 3)],
+        xy(zone.positions())
+    ];
+    assert_eq![
+        vec![(1, 2), (1, 3), (2, 2), (2, 3), (3, 2), (3, 3)],
+        xy(zone.positions_by(PositionOrder::ColumnMajor))
+    ];
+    assert_eq![
+        vec![(1, 2), (2, 2), (3, 2), (3, 3), (2, 3), (1, 3)],
+        xy(zone.positions_by(PositionOrder::Snake))
+    ];
+    assert_eq![
+        vec![(3, 3), (2, 3), (1, 3), (3, 2), (2, 2), (1, 2)],
+        xy(zone.positions().rev())
+    ];
+
+    let spiral: Vec<_> = Zone16::new_raw(0, 0, 4, 3)
+        .positions_by(PositionOrder::Spiral)
+        .map(|p| p.as_tuple())
+        .collect();
+    assert_eq![
+        vec![
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (3, 0),
+            (3, 1),
+            (3, 2),
+            (2, 2),
+            (1, 2),
+            (0, 2),
+            (0, 1),
+            (1, 1),
+            (2, 1)
+        ],
+        spiral
+    ];
+
+    let hilbert: Vec<_> = Zone32::new_raw(0, 0, 2, 2)
+        .positions_by(PositionOrder::Hilbert)
+        .map(|p| p.as_tuple())
+        .collect();
+    assert_eq![vec![(0, 0), (0, 1), (1, 1), (1, 0)], hilbert];
+
+    // the Hilbert curve adapts to elongated zones, taking unit steps
+    let long: Vec<_> = Zone16::new_raw(0, 0, 16_383, 3)
+        .positions_by(PositionOrder::Hilbert)
+        .collect();
+    assert_eq![16_383 * 3, long.len()];
+    assert![long.windows(2).all(|p| {
+        let (dx, dy) = (p[1].x() - p[0].x(), p[1].y() - p[0].y());
+        dx.abs() <= 1 && dy.abs() <= 1
+    })];
+
+    // every order visits every position exactly once, from both ends
+    let zone = Zone16::new_raw(-3, 5, 5, 7);
+    for order in [
+        PositionOrder::RowMajor,
+        PositionOrder::ColumnMajor,
+        PositionOrder::Snake,
+        PositionOrder::Spiral,
+        PositionOrder::Hilbert,
+    ] {
+        let mut iter = zone.positions_by(order);
+        assert_eq![35, iter.len()];
+        iter.next();
+        iter.next_back();
+        assert_eq![33, iter.len()];
+        let mut all: Vec<_> = zone.positions_by(order).collect();
+        let mut back: Vec<_> = zone.positions_by(order).rev().collect();
+        back.reverse();
+        assert_eq![all, back];
+        all.sort();
+        all.dedup();
+        assert_eq![35, all.len()];
+        assert![all.iter().all(|p| zone.contains_position(*p))];
+    }
+
+    // only representable positions are visited
+    assert_eq![
+        2,
+        Zone8::new_raw(Clamper8::MAX - 1, 0, 4, 1)
+            .positions()
+            .count()
+    ];
+
+    // too many positions for a usize
+    let (max, huge) = (
+        Clamper64::MAX,
+        Zone64::new_raw(0, 0, Clamper64::MAX, Clamper64::MAX),
+    );
+    assert_eq![(usize::MAX, None), huge.positions().size_hint()];
+    assert_eq![
+        Some(Position64::new(max - 1, max - 1)),
+        huge.positions().next_back()
+    ];
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn line() {
+    fn xy(iter: impl Iterator<Item = Position32>) -> Vec<(i32, i32)> {
+        iter.map(|p| p.as_tuple()).collect()
+    }
+    let (a, b) = (Position32::new(0, 0), Position32::new(5, 2));
+    assert_eq![
+        vec![(0, 0), (1, 0), (2, 1), (3, 1), (4, 2), (5, 2)],
+        xy(a.line_to(b))
+    ];
+    assert_eq![
+        vec![(5, 2), (4, 2), (3, 1), (2, 1), (1, 0), (0, 0)],
+        xy(b.line_to(a))
+    ];
+    assert_eq![
+        vec![(1, 0), (2, 1), (3, 1), (4, 2)],
+        xy(a.line_to(b).skip_start().skip_end())
+    ];
+    assert_eq![
+        vec![
+            (0, 0),
+            (1, 0),
+            (1, 1),
+            (2, 1),
+            (3, 1),
+            (4, 1),
+            (4, 2),
+            (5, 2)
+        ],
+        xy(a.line4_to(b))
+    ];
+    assert_eq![
+        vec![(0, 0), (1, 0), (1, 1), (2, 1), (2, 2)],
+        xy(a.line4_to(Position32::new(2, 2)))
+    ];
+    assert_eq![vec![(0, 0)], xy(a.line_to(a))];
+    assert_eq![0, a.line_to(a).skip_start().count()];
+    assert_eq![
+        (4, Some(4)),
+        a.line_to(b).skip_end().skip_start().size_hint()
+    ];
+
+    // extreme lines don't overflow
+    let (min, max) = (
+        Position16::new(i16::MIN, i16::MIN),
+        Position16::new(i16::MAX, 0),
+    );
+    let line = min.line4_to(max);
+    assert_eq![32_767 + 16_384 + 1, line.len()];
+    assert_eq![Some(max), line.last()];
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn ellipse() {
+    let center = Position16::new(10, 10);
+    let circle = Ellipse16::circle(center, 2);
+    let outline: Vec<_> = circle.outline().map(|p| p.as_tuple()).collect();
+    assert_eq![
+        vec![
+            (9, 8),
+            (10, 8),
+            (11, 8),
+            (8, 9),
+            (12, 9),
+            (8, 10),
+            (12, 10),
+            (8, 11),
+            (12, 11),
+            (9, 12),
+            (10, 12),
+            (11, 12)
+        ],
+        outline
+    ];
+    let spans: Vec<_> = circle.spans().collect();
+    assert_eq![
+        vec![
+            Zone16::new_raw(9, 8, 3, 1),
+            Zone16::new_raw(8, 9, 5, 1),
+            Zone16::new_raw(8, 10, 5, 1),
+            Zone16::new_raw(8, 11, 5, 1),
+            Zone16::new_raw(9, 12, 3, 1)
+        ],
+        spans
+    ];
+    assert_eq![
+        vec![(10, 10)],
+        Ellipse16::circle(center, 0)
+            .outline()
+            .map(|p| p.as_tuple())
+            .collect::<Vec<_>>()
+    ];
+
+    // every span cell is inside, and every outline cell is on a span
+    let ellipse = Ellipse32::new(Position32::new(-5, 3), Size32::new(9, 4));
+    assert_eq![9, ellipse.spans().len()];
+    let cells: Vec<_> = ellipse.spans().flat_map(|z| z.positions()).collect();
+    assert![cells.iter().all(|p| ellipse.contains_position(*p))];
+    assert![ellipse.outline().all(|p| cells.contains(&p))];
+    assert![ellipse
+        .spans()
+        .all(|z| z.h() == 1 && z.x() + z.x_end() - 1 == -10)];
+    assert_eq![
+        ellipse.spans().rev().collect::<Vec<_>>(),
+        ellipse
+            .spans()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .collect::<Vec<_>>()
+    ];
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn polygon() {
+    let p = |x, y| Position32::new(x, y);
+    let rect = [p(0, 0), p(4, 0), p(4, 3), p(0, 3)];
+    let spans: Vec<_> = PolygonSpans32::new(&rect, FillRule::EvenOdd).collect();
+    assert_eq![
+        vec![
+            Zone32::new_raw(0, 0, 4, 1),
+            Zone32::new_raw(0, 1, 4, 1),
+            Zone32::new_raw(0, 2, 4, 1)
+        ],
+        spans
+    ];
+
+    let triangle = [p(0, 0), p(4, 4), p(0, 4)];
+    let widths: Vec<_> = PolygonSpans32::new(&triangle, FillRule::NonZero)
+        .map(|z| (z.x(), z.w()))
+        .collect();
+    // the first row only touches the diagonal at a cell center
+    assert_eq![vec![(0, 1), (0, 2), (0, 3)], widths];
+
+    // the inner pentagon of a pentagram is only filled with non-zero winding
+    let star = [p(5, 0), p(8, 10), p(0, 4), p(10, 4), p(2, 10)];
+    let filled = |rule| PolygonSpans32::new(&star, rule).any(|z| z.contains_position(p(5, 5)));
+    assert![!filled(FillRule::EvenOdd)];
+    assert![filled(FillRule::NonZero)];
+    let even_odd: u32 = PolygonSpans32::new(&star, FillRule::EvenOdd)
+        .map(|z| z.w() as u32)
+        .sum();
+    let non_zero: u32 = PolygonSpans32::new(&star, FillRule::NonZero)
+        .map(|z| z.w() as u32)
+        .sum();
+    assert![even_odd < non_zero];
+
+    let clip = Zone32::new_raw(1, 1, 2, 10);
+    let clipped: Vec<_> = PolygonSpans32::new(&rect, FillRule::EvenOdd)
+        .clip(clip)
+        .collect();
+    assert_eq![
+        vec![Zone32::new_raw(1, 1, 2, 1), Zone32::new_raw(1, 2, 2, 1)],
+        clipped
+    ];
+    assert_eq![
+        0,
+        PolygonSpans32::new(&rect[..2], FillRule::EvenOdd).count()
+    ];
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn bezier() {
+    let p = |x, y| Position32::new(x, y);
+    let connected = |positions: &[Position32]| {
+        positions.windows(2).all(|w| {
+            let (dx, dy) = (w[1].x() - w[0].x(), w[1].y() - w[0].y());
+            dx.abs() <= 1 && dy.abs() <= 1 && (dx, dy) != (0, 0)
+        })
+    };
+
+    let curve: Vec<_> = Bezier32::quadratic(p(0, 0), p(10, 20), p(20, 0)).collect();
+    assert_eq![Some(&p(0, 0)), curve.first()];
+    assert_eq![Some(&p(20, 0)), curve.last()];
+    assert![connected(&curve)];
+    assert_eq![Some(10), curve.iter().map(|p| p.y()).max()];
+    assert![curve.contains(&p(10, 10))];
+
+    // a curve with aligned control points is a line
+    let straight: Vec<_> = Bezier32::cubic(p(0, 0), p(3, 1), p(6, 2), p(9, 3)).collect();
+    assert_eq![p(0, 0).line_to(p(9, 3)).collect::<Vec<_>>(), straight];
+
+    let cubic = Bezier32::cubic(p(0, 0), p(0, 30), p(30, -30), p(30, 0));
+    let coarse: Vec<_> = cubic.clone().tolerance(1_024).collect();
+    let fine: Vec<_> = cubic.tolerance(16).collect();
+    assert![connected(&coarse) && connected(&fine)];
+    assert_eq![coarse.last(), fine.last()];
+    assert_eq![
+        vec![p(5, 5)],
+        Bezier32::quadratic(p(5, 5), p(9, 9), p(5, 5))
+            .take(1)
+            .collect::<Vec<_>>()
+    ];
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn coord_generic() {
+    fn area<T: Coord>(zone: Zone<T>) -> i128
+    where
+        i128: From<T>,
+    {
+        i128::from(zone.w()) * i128::from(zone.h())
+    }
+    assert_eq![12, area(Zone8::new_raw(0, 0, 3, 4))];
+    assert_eq![63 * 63, area(Zone8::new_raw(0, 0, 100, 100))];
+    assert_eq![12, area(Zone64::from_xywh(-5, 5, 3, 4))];
+
+    // the geometry is generic too
+    fn uncovered<T: Coord>(zone: Zone<T>, others: &[Zone<T>]) -> usize {
+        others
+            .iter()
+            .filter(|o| !zone.contains_zone(**o))
+            .map(|o| o.difference(zone).len())
+            .sum()
+    }
+    let (a, b) = (Zone8::new_raw(0, 0, 4, 4), Zone8::new_raw(2, 2, 4, 4));
+    assert_eq![2, uncovered(a, &[b, a.intersection(b).unwrap()])];
+    let (a, b) = (Zone64::new_raw(0, 0, 4, 4), Zone64::new_raw(2, 2, 4, 4));
+    assert_eq![4, uncovered(a, &[b, a.union(b)])];
+
+    assert_eq![Position16::new(-16_384, 7), Position::from_xy(i16::MIN, 7)];
+    assert_eq![Size32::new(1, 9), Size::from_wh(-3, 9)];
+    assert_eq![Clamper64::MAX, <i64 as Coord>::CLAMP_MAX];
+
+    assert_eq![
+        "Zone8 { p: Position8 { x: 1, y: 2 }, s: Size8 { w: 3, h: 4 } }",
+        format!["{:?}", Zone8::new_raw(1, 2, 3, 4)]
+    ];
+}
+
+#[test]
+fn convert_widths() {
+    // widening is lossless
+    let z8 = Zone8::new_raw(-64, 63, 63, 1);
+    let z64: Zone64 = z8.into();
+    assert_eq![(-64, 63, 63, 1), z64.as_tuple()];
+    assert_eq![
+        Position32::new(-16_384, 16_383),
+        Position16::new(i16::MIN, i16::MAX).into()
+    ];
+
+    // narrowing fails out of range, or clamps
+    assert_eq![Ok(z8), Zone8::try_from(z64)];
+    let big = Zone32::new_raw(-100, 5, 1_000, 10);
+    assert_eq![Err(OutOfRangeError), Zone8::try_from(big)];
+    assert_eq![
+        Err(OutOfRangeError),
+        Size16::try_from(Size32::new(16_384, 1))
+    ];
+    assert_eq![Zone8::new_raw(-64, 5, 63, 10), big.clamp_into()];
+    assert_eq![
+        Position8::new(-64, 63),
+        Position8::clamp_from(Position64::new(i64::MIN, i64::MAX))
+    ];
+    let same: Size16 = Size16::new(3, 4).clamp_into();
+    assert_eq![Size16::new(3, 4), same];
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_forms() {
+    use serde_test::{assert_de_tokens, assert_tokens, Token};
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Compact {
+        #[serde(with = "crate::compact")]
+        zone: Zone8,
+    }
+
+    let p = |x, y| Position8::new(x, y);
+    assert_tokens(
+        &p(1, -2),
+        &[
+            Token::Struct {
+                name: "Position",
+                len: 2,
+            },
+            Token::Str("x"),
+            Token::I8(1),
+            Token::Str("y"),
+            Token::I8(-2),
+            Token::StructEnd,
+        ],
+    );
+    // out of range data is clamped
+    assert_de_tokens(
+        &Size8::new(63, 1),
+        &[
+            Token::Struct {
+                name: "Size",
+                len: 2,
+            },
+            Token::Str("w"),
+            Token::I8(100),
+            Token::Str("h"),
+            Token::I8(-5),
+            Token::StructEnd,
+        ],
+    );
+    assert_tokens(
+        &Zone16::new_raw(1, 2, 3, 4),
+        &[
+            Token::Struct {
+                name: "Zone",
+                len: 2,
+            },
+            Token::Str("p"),
+            Token::Struct {
+                name: "Position",
+                len: 2,
+            },
+            Token::Str("x"),
+            Token::I16(1),
+            Token::Str("y"),
+            Token::I16(2),
+            Token::StructEnd,
+            Token::Str("s"),
+            Token::Struct {
+                name: "Size",
+                len: 2,
+            },
+            Token::Str("w"),
+            Token::I16(3),
+            Token::Str("h"),
+            Token::I16(4),
+            Token::StructEnd,
+            Token::StructEnd,
+        ],
+    );
+
+    let compact = Compact {
+        zone: Zone8::new_raw(-1, 2, 3, 4),
+    };
+    let tokens = |x| {
+        [
+            Token::Struct {
+                name: "Compact",
+                len: 1,
+            },
+            Token::Str("zone"),
+            Token::Tuple { len: 4 },
+            Token::I8(x),
+            Token::I8(2),
+            Token::I8(3),
+            Token::I8(4),
+            Token::TupleEnd,
+            Token::StructEnd,
+        ]
+    };
+    assert_tokens(&compact, &tokens(-1));
+    let clamped = Compact {
+        zone: Zone8::new_raw(-64, 2, 3, 4),
+    };
+    assert_de_tokens(&clamped, &tokens(-128));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn parse_strings() {
+    use ParseErrorKind::*;
+    let err = |c, kind| (Some(c), kind);
+    let fail = |e: ParseError| (e.component(), e.kind());
+
+    // the display output parses back
+    for zone in [
+        Zone16::new_raw(-3, 5, 7, 9),
+        Zone16::new_raw(0, 16_383, 1, 16_383),
+    ] {
+        assert_eq![Ok(zone), format!["{}", zone].parse()];
+        assert_eq![Ok(zone.p), format!["{}", zone.p].parse()];
+        assert_eq![Ok(zone.s), format!["{}", zone.s].parse()];
+    }
+    let insets = Insets8::new(1, 2, 3, 4);
+    assert_eq![Ok(insets), format!["{}", insets].parse()];
+    assert_eq![Ok(insets), "1, 2, 3, 4".parse()];
+
+    assert_eq![Ok(Position32::new(10, -20)), " 10, -20 ".parse()];
+    assert_eq![Ok(Size8::new(30, 40)), "30x40".parse()];
+    assert_eq![Ok(Size8::new(30, 40)), "30,40".parse()];
+    assert_eq![Ok(Zone32::new_raw(1, 2, 3, 4)), "1,2,3,4".parse()];
+    assert_eq![Ok(Zone32::new_raw(10, -20, 30, 40)), "30x40+10-20".parse()];
+    assert_eq![Ok(Zone32::new_raw(-10, 20, 30, 40)), "30x40+-10+20".parse()];
+
+    // errors
+    assert_eq![
+        err(Component::Y, Missing),
+        fail("1".parse::<Position8>().unwrap_err())
+    ];
+    assert_eq![
+        err(Component::X, Invalid),
+        fail("a,1".parse::<Position8>().unwrap_err())
+    ];
+    assert_eq![
+        err(Component::X, Clamped),
+        fail("64,1".parse::<Position8>().unwrap_err())
+    ];
+    assert_eq![
+        err(Component::X, Clamped),
+        fail("999,1".parse::<Position8>().unwrap_err())
+    ];
+    assert_eq![
+        err(Component::H, Clamped),
+        fail("3x0".parse::<Size8>().unwrap_err())
+    ];
+    assert_eq![
+        err(Component::Left, Clamped),
+        fail("1,2,3,-4".parse::<Insets8>().unwrap_err())
+    ];
+    assert_eq![
+        (None, Format),
+        fail("x:1 w:2".parse::<Position8>().unwrap_err())
+    ];
+    assert_eq![(None, Format), fail("1,2,3".parse::<Size8>().unwrap_err())];
+    assert_eq![
+        (None, Format),
+        fail("30x40+10".parse::<Zone8>().unwrap_err())
+    ];
+    for geometry in ["3x4++1+2", "3x4+-+-1+2", "3x4--1+2", "3x4+1+2+", "3x4+1-"] {
+        assert_eq![(None, Format), fail(geometry.parse::<Zone8>().unwrap_err())];
+    }
+    assert!["3x0".parse::<Size8>().unwrap_err().would_clamp()];
+}
+
+#[test]
+fn offset() {
+    let (a, b) = (Position8::new(-64, 10), Position8::new(63, -5));
+    let d = b - a;
+    assert_eq![Offset8::new(127, -15), d];
+    assert_eq![b, a + d];
+    assert_eq![a, b - d];
+    assert_eq![d, a.offset_to(b)];
+    assert_eq![Offset8::new(-127, 15), -d];
+
+    // translations are clamped, scaling saturates
+    assert_eq![Position8::new(63, -20), b + d];
+    assert_eq![Offset16::new(6, -4), Offset16::new(3, -2) * 2];
+    assert_eq![Offset8::new(127, -30), d * 2];
+    assert_eq![Offset8::new(-128, 127), Offset8::new(-100, 100) * 9];
+    assert_eq![
+        Offset32::new(4, 1),
+        Offset32::new(3, -2) + Offset32::new(1, 3)
+    ];
+
+    assert_eq![Size8::new(63, 15), Size8::from_offset(d)];
+    assert_eq![Size8::new(1, 1), Size8::from_offset(Offset8::default())];
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn directions_neighbors() {
+    use Direction4 as D4;
+    use Direction8 as D8;
+    let p = |x, y| Position16::new(x, y);
+
+    assert_eq![D4::Right, D4::Up.cw()];
+    assert_eq![D4::Left, D4::Up.ccw()];
+    assert_eq![D4::Right, D4::Left.opposite()];
+    assert_eq![D8::UpLeft, D8::Up.ccw()];
+    assert_eq![D8::DownLeft, D8::UpRight.opposite()];
+    assert_eq![None, D8::DownRight.to_direction4()];
+    assert_eq![Some(D4::Down), D8::Down.to_direction4()];
+    assert_eq![Offset32::new(-1, 1), D8::DownLeft.offset()];
+    assert_eq![p(0, -1), D4::Up.position()];
+    assert_eq![p(4, 4), p(5, 5).step(D8::UpLeft)];
+
+    let n4: Vec<_> = p(5, 5).neighbors4().collect();
+    assert_eq![vec![p(5, 4), p(6, 5), p(5, 6), p(4, 5)], n4];
+    assert_eq![8, p(5, 5).neighbors8().count()];
+
+    // bounded by a zone, and by the clamped range
+    let zone = Zone16::new_raw(0, 0, 10, 10);
+    let corner: Vec<_> = p(0, 0).neighbors8().within(zone).collect();
+    assert_eq![vec![p(1, 0), p(1, 1), p(0, 1)], corner];
+    assert_eq![2, p(16_383, -16_384).neighbors4().count()];
+}
@@ -1,10 +1,274 @@
 // cuadra::zone
 //
-//!
+//! 2D zones.
 //
 
+use crate::{Coord, Position, Size};
 use core::fmt;
 
+/// A 2D zone combines a [`Position`] with a [`Size`].
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct Zone<T> {
+    pub p: Position<T>,
+    pub s: Size<T>,
+}
+
+impl<T: Coord> fmt::Debug for Zone<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Zone{} {{ p: {:?}, s: {:?} }}", T::BITS, self.p, self.s,)
+    }
+}
+
+impl<T: Coord> fmt::Display for Zone<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.p, self.s)
+    }
+}
+
+impl<T: Coord> Zone<T> {
+    /// Returns a new zone from the provided position and size raw components,
+    /// for any [`Coord`].
+    pub fn from_xywh(x: T, y: T, width: T, height: T) -> Self {
+        Self {
+            p: Position::from_xy(x, y),
+            s: Size::from_wh(width, height),
+        }
+    }
+
+    /// Gets a tuple with the position and size.
+    pub const fn position_size(&self) -> (Position<T>, Size<T>) {
+        (self.p, self.s)
+    }
+
+    /// Get the position.
+    pub const fn position(&self) -> Position<T> {
+        self.p
+    }
+    /// Chain-set the position.
+    pub fn set_position(mut self, position: impl Into<Position<T>>) {
+        self.p = position.into();
+    }
+
+    /// Get the size.
+    pub const fn size(&self) -> Size<T> {
+        self.s
+    }
+    /// Set the size.
+    pub fn set_size(mut self, size: impl Into<Size<T>>) {
+        self.s = size.into();
+    }
+
+    /// Get the `x` position.
+    pub const fn x(&self) -> T {
+        self.p.x()
+    }
+    /// Set the `x` position.
+    pub fn set_x(&mut self, x: T) {
+        self.p.set_x(x)
+    }
+
+    /// Get the `y` position.
+    pub const fn y(&self) -> T {
+        self.p.y()
+    }
+    /// Set the `y` position.
+    pub fn set_y(&mut self, y: T) {
+        self.p.set_y(y)
+    }
+
+    /// Get the `width`.
+    pub const fn w(&self) -> T {
+        self.s.w()
+    }
+    /// Set the `width`.
+    pub fn set_w(&mut self, width: T) {
+        self.s.set_w(width)
+    }
+
+    /// Get the `height`.
+    pub const fn h(&self) -> T {
+        self.s.h()
+    }
+    /// Set the `height`.
+    pub fn set_h(&mut self, height: T) {
+        self.s.set_h(height)
+    }
+}
+
+/// # geometry
+///
+/// A zone covers the positions from its origin up to, but not including,
+/// its origin plus its size. Zones touching at an edge don't overlap.
+impl<T: Coord> Zone<T> {
+    /// Returns the `x` coordinate right after the last column of the zone.
+    ///
+    /// It's always representable thanks to the clamped safety margin.
+    #[inline]
+    pub fn x_end(&self) -> T {
+        self.x().saturating_add(self.w())
+    }
+    /// Returns the `y` coordinate right after the last row of the zone.
+    ///
+    /// It's always representable thanks to the clamped safety margin.
+    #[inline]
+    pub fn y_end(&self) -> T {
+        self.y().saturating_add(self.h())
+    }
+
+    /// Returns `true` if the `position` is inside the zone.
+    pub fn contains_position(&self, position: Position<T>) -> bool {
+        position.x() >= self.x()
+            && position.x() < self.x_end()
+            && position.y() >= self.y()
+            && position.y() < self.y_end()
+    }
+
+    /// Returns `true` if the `other` zone is completely inside this zone.
+    pub fn contains_zone(&self, other: Zone<T>) -> bool {
+        other.x() >= self.x()
+            && other.x_end() <= self.x_end()
+            && other.y() >= self.y()
+            && other.y_end() <= self.y_end()
+    }
+
+    /// Returns `true` if both zones share at least one position.
+    ///
+    /// Zones that only touch at an edge don't overlap.
+    pub fn overlaps(&self, other: Zone<T>) -> bool {
+        self.x() < other.x_end()
+            && other.x() < self.x_end()
+            && self.y() < other.y_end()
+            && other.y() < self.y_end()
+    }
+
+    /// Returns the zone shared by both zones,
+    /// or `None` if they don't [`overlap`][Self::overlaps].
+    pub fn intersection(&self, other: Zone<T>) -> Option<Zone<T>> {
+        if !self.overlaps(other) {
+            return None;
+        }
+        let (x, y) = (self.x().max(other.x()), self.y().max(other.y()));
+        let x_end = self.x_end().min(other.x_end());
+        let y_end = self.y_end().min(other.y_end());
+        Some(Zone::from_xywh(
+            x,
+            y,
+            x_end.saturating_sub(x),
+            y_end.saturating_sub(y),
+        ))
+    }
+
+    /// Returns the bounding box of both zones.
+    ///
+    /// The resulting size is clamped, so the bounding box of zones
+    /// that are very far apart may not completely cover both of them.
+    pub fn union(&self, other: Zone<T>) -> Zone<T> {
+        let (x, y) = (self.x().min(other.x()), self.y().min(other.y()));
+        let x_end = self.x_end().max(other.x_end());
+        let y_end = self.y_end().max(other.y_end());
+        Zone::from_xywh(x, y, x_end.saturating_sub(x), y_end.saturating_sub(y))
+    }
+
+    /// Returns the parts of this zone not covered by the `other` zone.
+    ///
+    /// The result has between zero and four non-overlapping zones,
+    /// ordered as: the band above `other`, the parts to its left and
+    /// right, and the band below it.
+    ///
+    /// Parts that would start beyond the clamped maximum are left out,
+    /// since their position can't be represented.
+    pub fn difference(&self, other: Zone<T>) -> ZoneDifference<T> {
+        let mut diff = ZoneDifference::new();
+        let inter = match self.intersection(other) {
+            Some(inter) => inter,
+            None => {
+                diff.push(*self);
+                return diff;
+            }
+        };
+
+        if inter.y() > self.y() {
+            let h = inter.y().saturating_sub(self.y());
+            diff.push(Zone::from_xywh(self.x(), self.y(), self.w(), h));
+        }
+        if inter.x() > self.x() {
+            let w = inter.x().saturating_sub(self.x());
+            diff.push(Zone::from_xywh(self.x(), inter.y(), w, inter.h()));
+        }
+        if inter.x_end() < self.x_end() && inter.x_end() <= T::CLAMP_MAX {
+            let w = self.x_end().saturating_sub(inter.x_end());
+            diff.push(Zone::from_xywh(inter.x_end(), inter.y(), w, inter.h()));
+        }
+        if inter.y_end() < self.y_end() && inter.y_end() <= T::CLAMP_MAX {
+            let h = self.y_end().saturating_sub(inter.y_end());
+            diff.push(Zone::from_xywh(self.x(), inter.y_end(), self.w(), h));
+        }
+        diff
+    }
+}
+
+/// Up to four non-overlapping [`Zone`]s, returned by [`Zone::difference`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ZoneDifference<T> {
+    zones: [Zone<T>; 4],
+    len: usize,
+}
+
+impl<T: Coord> fmt::Debug for ZoneDifference<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Coord> ZoneDifference<T> {
+    fn new() -> Self {
+        Self {
+            zones: [Zone::default(); 4],
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, zone: Zone<T>) {
+        self.zones[self.len] = zone;
+        self.len += 1;
+    }
+
+    /// Returns the number of zones.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+    /// Returns `true` if there are no zones.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the zones as a slice.
+    pub fn as_slice(&self) -> &[Zone<T>] {
+        &self.zones[..self.len]
+    }
+    /// Returns an iterator over the zones.
+    pub fn iter(&self) -> core::slice::Iter<'_, Zone<T>> {
+        self.as_slice().iter()
+    }
+}
+
+impl<T: Coord> IntoIterator for ZoneDifference<T> {
+    type Item = Zone<T>;
+    type IntoIter = core::iter::Take<core::array::IntoIter<Zone<T>, 4>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.zones.into_iter().take(self.len)
+    }
+}
+impl<'a, T: Coord> IntoIterator for &'a ZoneDifference<T> {
+    type Item = &'a Zone<T>;
+    type IntoIter = core::slice::Iter<'a, Zone<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 macro_rules! zone {
     ( $($i:ty, $b:expr),+ ) => {
         $( zone![single: $i, $b]; )+
@@ -14,23 +278,11 @@ macro_rules! zone {
         use super::{[<Position$b>], [<Size$b>]};
 
         #[doc = "A 2D zone combines a [`" [<Position$b>] "`] with a [`" [<Size$b>] "`]."]
-        #[derive(Clone, Copy, Default, PartialEq, Eq)]
-        pub struct [<Zone$b>] {
-            pub p: [<Position$b>],
-            pub s: [<Size$b>],
-        }
+        pub type [<Zone$b>] = Zone<$i>;
 
-        impl fmt::Debug for [<Zone$b>] {
-            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                write!(f, "[<Zone$b>] {{ p: {:?}, s: {:?} }}", self.p, self.s,)
-            }
-        }
-
-        impl fmt::Display for [<Zone$b>] {
-            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                write!(f, "{} {}", self.p, self.s)
-            }
-        }
+        #[doc = "Up to four non-overlapping [`" [<Zone$b>] "`]s,"]
+        #[doc = "returned by [`" [<Zone$b>] "::difference`]."]
+        pub type [<ZoneDifference$b>] = ZoneDifference<$i>;
 
         impl [<Zone$b>] {
             /// Returns a new zone from the provided position and size.
@@ -45,65 +297,6 @@ macro_rules! zone {
             pub const fn new_raw(x: $i, y: $i, width: $i, height: $i) -> Self {
                 Self::new([<Position$b>]::new(x, y), [<Size$b>]::new(width, height))
             }
-
-            /// Gets a tuple with the position and size.
-            pub const fn position_size(&self) -> ([<Position$b>], [<Size$b>]) {
-                (self.p, self.s)
-            }
-
-            /// Get the position.
-            pub const fn position(&self) -> [<Position$b>] {
-                self.p
-            }
-            /// Chain-set the position.
-            pub fn set_position(mut self, position: impl Into<[<Position$b>]>) {
-                self.p = position.into();
-            }
-
-            /// Get the size.
-            pub const fn size(&self) -> [<Size$b>] {
-                self.s
-            }
-            /// Set the size.
-            pub fn set_size(mut self, size: impl Into<[<Size$b>]>) {
-                self.s = size.into();
-            }
-
-            /// Get the `x` position.
-            pub const fn x(&self) -> $i {
-                self.p.x()
-            }
-            /// Set the `x` position.
-            pub fn set_x(&mut self, x: $i) {
-                self.p.set_x(x)
-            }
-
-            /// Get the `y` position.
-            pub const fn y(&self) -> $i {
-                self.p.y()
-            }
-            /// Set the `y` position.
-            pub fn set_y(&mut self, y: $i) {
-                self.p.set_y(y)
-            }
-
-            /// Get the `width`.
-            pub const fn w(&self) -> $i {
-                self.s.w()
-            }
-            /// Set the `width`.
-            pub fn set_w(&mut self, width: $i) {
-                self.s.set_w(width)
-            }
-
-            /// Get the `height`.
-            pub const fn h(&self) -> $i {
-                self.s.h()
-            }
-            /// Set the `height`.
-            pub fn set_h(&mut self, height: $i) {
-                self.s.set_h(height)
-            }
         }
 
         /// # conversions